@@ -0,0 +1,37 @@
+//! Times a frame of combat for bigger and bigger battles.
+//!
+//! `cargo bench --bench combat`
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use paperclips::{PaperClips, combat::ShipCount, rng::PCRng};
+
+/// Frames of each battle, most ships are still alive by then
+const FRAMES: u32 = 50;
+const BATTLES: u32 = 20;
+
+fn main() {
+    for ship_cap in [200, 2_000, 10_000] as [ShipCount; 3] {
+        let mut pc = PaperClips { rng: PCRng::seeded(0), ..Default::default() };
+        pc.space.probe_count = 1e15;
+        pc.space.drifter_count = 1e15;
+        pc.combat.ship_cap = ship_cap;
+
+        let mut elapsed = Duration::ZERO;
+        let mut ships = 0;
+        for _ in 0..BATTLES {
+            pc.create_battle();
+            ships += pc.combat.ships.len();
+            let start = Instant::now();
+            for _ in 0..FRAMES {
+                black_box(&mut pc).update_combat();
+            }
+            elapsed += start.elapsed();
+        }
+        println!(
+            "cap {ship_cap:>6}: {:>6} ships on average, {:>10.2?} per frame",
+            ships / BATTLES as usize,
+            elapsed / (FRAMES * BATTLES),
+        );
+    }
+}
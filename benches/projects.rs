@@ -0,0 +1,47 @@
+//! Compares `manage_projects` with the scan over every project it replaced, alone and within whole ticks.
+//!
+//! `cargo bench --bench projects`
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use paperclips::{PaperClips, player::Team, project::ProjectStatus, rng::PCRng};
+
+const ITERATIONS: u32 = 200_000;
+
+/// Average time of `f` over `ITERATIONS` calls on copies of `pc`
+fn time(pc: &PaperClips, mut f: impl FnMut(&mut PaperClips)) -> Duration {
+    let mut pc = pc.clone();
+    // the first call builds the watch list
+    f(&mut pc);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f(black_box(&mut pc));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn bench(name: &str, pc: &PaperClips) {
+    let scan = time(pc, |pc| pc.manage_projects_scan());
+    let indexed = time(pc, |pc| pc.manage_projects());
+    let tick_scan = time(pc, |pc| pc.main_tick_scan());
+    let tick = time(pc, |pc| pc.main_tick());
+    println!(
+        "{name:<24} scan {scan:>10.2?}  indexed {indexed:>10.2?}  ({:.1}x)  main_tick {tick_scan:>10.2?} -> {tick:>10.2?}  ({:.1}x)",
+        scan.as_secs_f64() / indexed.as_secs_f64(),
+        tick_scan.as_secs_f64() / tick.as_secs_f64(),
+    );
+}
+
+fn main() {
+    let mut pc = PaperClips { rng: PCRng::seeded(0), ..Default::default() };
+    bench("new game", &pc);
+
+    let mut team = Team::reference();
+    for ticks in [60_000, 300_000, 1_000_000] {
+        while pc.ticks < ticks {
+            pc.step_with(&mut team);
+        }
+        let bought = pc.projects.statuses().iter().filter(|&&status| status == ProjectStatus::Bought).count();
+        bench(&format!("{ticks} ticks, {bought} bought"), &pc);
+    }
+}
@@ -0,0 +1,111 @@
+//! Plays many seeded games on all the cores and prints how their outcomes are distributed.
+
+use std::{io::Write, path::PathBuf, process::ExitCode, time::Instant};
+
+use paperclips::{PaperClips, batch::Batch};
+
+const USAGE: &str = "\
+Usage: batch [OPTIONS]
+
+Options:
+  -n, --games <N>        Number of games [default: 100]
+  -t, --max-ticks <N>    Ticks each game runs for at most [default: 360000 (1 hour)]
+  -g, --goal <GOAL>      Stop the games once they reach milestone:<N>, post-human, space, endgame or credits
+  -s, --seed <SEED>      Seed of the first game, the next ones count up from it [default: 0]
+  -j, --threads <N>      [default: every core]
+  -l, --load <PATH>      Start every game from a save made by this game
+      --idle             Don't let the reference bots play
+  -o, --csv <PATH>       Save one line per game to PATH
+  -q, --quiet            Don't print the progress
+  -h, --help             Print this message
+";
+
+struct Args {
+    batch: Batch,
+    load: Option<PathBuf>,
+    csv: Option<PathBuf>,
+    quiet: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        batch: Batch::new(100, 360_000),
+        load: None,
+        csv: None,
+        quiet: false,
+    };
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("missing value for `{arg}`"));
+        macro_rules! parse {
+            ($what:literal) => {{
+                let value = value()?;
+                value.replace('_', "").parse().map_err(|e| format!("invalid {} `{value}`: {e}", $what))?
+            }};
+        }
+        match arg.as_str() {
+            "-n" | "--games" => args.batch.games = parse!("game count"),
+            "-t" | "--max-ticks" => args.batch.max_ticks = parse!("tick count"),
+            "-g" | "--goal" => args.batch.goal = Some(value()?.parse()?),
+            "-s" | "--seed" => args.batch.seed = parse!("seed"),
+            "-j" | "--threads" => args.batch.threads = parse!("thread count"),
+            "-l" | "--load" => args.load = Some(value()?.into()),
+            "--idle" => args.batch.bots = false,
+            "-o" | "--csv" => args.csv = Some(value()?.into()),
+            "-q" | "--quiet" => args.quiet = true,
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                std::process::exit(0);
+            },
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+
+    if args.batch.games == 0 || args.batch.threads == 0 {
+        return Err("the game count and the thread count must be at least 1".to_string());
+    }
+
+    Ok(args)
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+    let initial = match &args.load {
+        Some(path) => PaperClips::load_from_file(path).map_err(|e| format!("couldn't load {}: {e}", path.display()))?,
+        None => PaperClips::default(),
+    };
+    let batch = args.batch;
+
+    let start = Instant::now();
+    let report = batch.run(&initial, |done| {
+        if !args.quiet {
+            eprint!("\r{done}/{} games", batch.games);
+            let _ = std::io::stderr().flush();
+        }
+    });
+    if !args.quiet {
+        eprintln!();
+    }
+    let ticks: u128 = report.results.iter().map(|result| result.ticks).sum();
+    println!("Played {} games ({ticks} ticks) on {} threads in {:.2?}", batch.games, batch.threads, start.elapsed());
+    println!();
+    print!("{report}");
+
+    if let Some(path) = &args.csv {
+        std::fs::write(path, report.to_csv()).map_err(|e| format!("couldn't save {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            eprint!("\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
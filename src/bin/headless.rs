@@ -0,0 +1,235 @@
+//! Runs the game without a window, as fast as the CPU allows.
+
+use std::{path::PathBuf, process::ExitCode, time::Instant};
+
+use paperclips::{PaperClips, Ticks, player::Team, rates::Flow, replay::Replay, rng::PCRng, save::SaveFile, telemetry::{DEFAULT_TELEMETRY_INTERVAL, Telemetry}, util::{number_cruncher, ticks_10ms, ticks_to_duration, time_cruncher}, warp::OFFLINE_BUDGET};
+
+const USAGE: &str = "\
+Usage: headless [OPTIONS]
+
+Options:
+  -t, --ticks <N>      Number of 10ms ticks to run [default: 360000 (1 hour), 0 with --replay]
+  -s, --seed <SEED>    Seed of the rng, for reproducible runs
+  -l, --load <PATH>    Start from a save made by this game
+      --offline        Catch up on the time since the loaded save was made
+      --skip <N>       Skip N ticks before running, only moving operations, creativity and wire price
+  -i, --import <PATH>  Start from the localStorage of the web version (JSON)
+  -r, --replay <PATH>  Start from the end of a replay
+  -o, --save <PATH>    Save the final state to PATH
+      --dump           Print the final state to stdout
+  -b, --bot            Let the reference bots play
+  -e, --events         Print game events as they happen
+      --log <PATH>     Export the console history to PATH (.txt or .json)
+      --telemetry <PATH>
+                       Export a row of stats every interval to PATH (.csv or .json)
+      --interval <N>   Ticks between telemetry rows [default: 100]
+  -q, --quiet          Don't print the summary
+  -h, --help           Print this message
+";
+
+struct Args {
+    ticks: Option<Ticks>,
+    seed: Option<u64>,
+    load: Option<PathBuf>,
+    import: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    save: Option<PathBuf>,
+    log: Option<PathBuf>,
+    telemetry: Option<PathBuf>,
+    interval: Ticks,
+    skip: Ticks,
+    offline: bool,
+    dump: bool,
+    bot: bool,
+    events: bool,
+    quiet: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        ticks: None,
+        seed: None,
+        load: None,
+        import: None,
+        replay: None,
+        save: None,
+        log: None,
+        telemetry: None,
+        interval: DEFAULT_TELEMETRY_INTERVAL,
+        skip: 0,
+        offline: false,
+        dump: false,
+        bot: false,
+        events: false,
+        quiet: false,
+    };
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("missing value for `{arg}`"));
+        match arg.as_str() {
+            "-t" | "--ticks" => {
+                let value = value()?;
+                args.ticks = Some(value.replace('_', "").parse().map_err(|e| format!("invalid tick count `{value}`: {e}"))?);
+            },
+            "-s" | "--seed" => {
+                let value = value()?;
+                args.seed = Some(value.parse().map_err(|e| format!("invalid seed `{value}`: {e}"))?);
+            },
+            "-l" | "--load" => args.load = Some(value()?.into()),
+            "--offline" => args.offline = true,
+            "--skip" => {
+                let value = value()?;
+                args.skip = value.replace('_', "").parse().map_err(|e| format!("invalid tick count `{value}`: {e}"))?;
+            },
+            "-i" | "--import" => args.import = Some(value()?.into()),
+            "-r" | "--replay" => args.replay = Some(value()?.into()),
+            "-o" | "--save" => args.save = Some(value()?.into()),
+            "--log" => args.log = Some(value()?.into()),
+            "--telemetry" => args.telemetry = Some(value()?.into()),
+            "--interval" => {
+                let value = value()?;
+                args.interval = value.replace('_', "").parse().map_err(|e| format!("invalid interval `{value}`: {e}"))?;
+                if args.interval == 0 {
+                    return Err("the interval must be at least 1 tick".to_string());
+                }
+            },
+            "--dump" => args.dump = true,
+            "-b" | "--bot" => args.bot = true,
+            "-e" | "--events" => args.events = true,
+            "-q" | "--quiet" => args.quiet = true,
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                std::process::exit(0);
+            },
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+
+    if [args.load.is_some(), args.import.is_some(), args.replay.is_some()].into_iter().filter(|&b| b).count() > 1 {
+        return Err("only one of `--load`, `--import` and `--replay` can be used".to_string());
+    }
+    if args.offline && args.load.is_none() {
+        return Err("`--offline` needs a save to `--load`".to_string());
+    }
+
+    Ok(args)
+}
+
+fn load_state(args: &Args) -> Result<PaperClips, String> {
+    if let Some(path) = &args.load {
+        let save = SaveFile::load_from_file(path).map_err(|e| format!("couldn't load {}: {e}", path.display()))?;
+        let away = save.time_away();
+        let mut pc = save.paperclips;
+        if args.offline && let Some(away) = away {
+            let catch_up = pc.catch_up(ticks_10ms(away), OFFLINE_BUDGET);
+            if !args.quiet {
+                println!("Caught up on {:.2?} away: {catch_up}", away);
+            }
+        }
+        Ok(pc)
+    } else if let Some(path) = &args.import {
+        let blob = std::fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        PaperClips::from_js_save(&blob).map_err(|e| format!("couldn't import {}: {e}", path.display()))
+    } else if let Some(path) = &args.replay {
+        let replay = Replay::load_from_file(path).map_err(|e| format!("couldn't load {}: {e}", path.display()))?;
+        Ok(replay.play())
+    } else {
+        Ok(PaperClips::default())
+    }
+}
+
+fn print_summary(pc: &PaperClips, ran: Ticks, start_ticks: Ticks, elapsed: std::time::Duration) {
+    let speed = ran as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    let game_time = |ticks: Ticks| time_cruncher(ticks_to_duration(ticks)).trim_end().to_string();
+
+    println!("Ran {ran} ticks ({}) in {:.2?}, {} ticks/s", game_time(ran), elapsed, number_cruncher(speed, Some(1)));
+    println!("Game time: {} (from tick {start_ticks} to {})", game_time(pc.ticks), pc.ticks);
+    println!();
+    println!("Stage:      {} ({} milestones)", pc.progression, pc.milestones);
+    println!("Clips:      {} ({}/s)", number_cruncher(pc.business.clips, Some(1)), number_cruncher(pc.rates.per_second(Flow::ClipsCreated), Some(1)));
+    if pc.human_flag() {
+        println!("Unsold:     {}", number_cruncher(pc.business.unsold_clips, Some(1)));
+        println!("Funds:      ${}", number_cruncher(pc.business.funds, Some(2)));
+        println!("Wire:       {}", number_cruncher(pc.wire.count, Some(1)));
+        println!("Trust:      {}", pc.computational.trust);
+    }
+    println!("Processors: {}, Memory: {}", pc.computational.processors, pc.computational.memory);
+    println!("Operations: {}, Creativity: {}", number_cruncher(pc.computational.operations, Some(0)), number_cruncher(pc.computational.creativity, Some(0)));
+    if pc.strategy.engine_flag {
+        println!("Yomi:       {}", number_cruncher(pc.strategy.yomi, Some(0)));
+    }
+    if pc.space_flag() {
+        println!("Probes:     {} (+{}/s)", number_cruncher(pc.space.probe_count, Some(1)), number_cruncher(pc.rates.per_second(Flow::ProbesSpawned), Some(1)));
+        println!("Drifters:   {} (+{}/s)", number_cruncher(pc.space.drifter_count, Some(1)), number_cruncher(pc.rates.per_second(Flow::DriftersCreated), Some(1)));
+        println!("Honor:      {}", pc.combat.honor);
+    }
+    println!();
+    for message in &pc.console.messages {
+        println!("> {message}");
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+    let mut pc = load_state(&args)?;
+    if let Some(seed) = args.seed {
+        pc.rng = PCRng::seeded(seed);
+    }
+    let ticks = args.ticks.unwrap_or(if args.replay.is_some() { 0 } else { 360_000 });
+    pc.skip(args.skip);
+
+    let mut telemetry = args.telemetry.as_ref().map(|_| Telemetry::new(args.interval));
+    if let Some(telemetry) = &mut telemetry {
+        telemetry.record(&pc);
+    }
+
+    let start_ticks = pc.ticks;
+    let start = Instant::now();
+    let mut bot = args.bot.then(Team::reference);
+    for _ in 0..ticks {
+        match &mut bot {
+            Some(bot) => pc.step_with(bot),
+            None => pc.step(),
+        }
+        if let Some(telemetry) = &mut telemetry {
+            telemetry.record(&pc);
+        }
+        if args.events {
+            for (ticks, event) in pc.take_events() {
+                println!("[{ticks:>9}] {event}");
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+
+    if !args.quiet {
+        print_summary(&pc, ticks, start_ticks, elapsed);
+    }
+    if args.dump {
+        println!("{}", pc.to_save_string().map_err(|e| e.to_string())?);
+    }
+    if let Some(path) = &args.save {
+        pc.save_to_file(path).map_err(|e| format!("couldn't save {}: {e}", path.display()))?;
+    }
+    if let (Some(path), Some(telemetry)) = (&args.telemetry, &telemetry) {
+        telemetry.export(path).map_err(|e| format!("couldn't export the telemetry to {}: {e}", path.display()))?;
+    }
+    if let Some(path) = &args.log {
+        pc.console.export_history(path).map_err(|e| format!("couldn't export the console to {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            eprint!("\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
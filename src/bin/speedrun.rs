@@ -0,0 +1,150 @@
+//! Searches for the parameters of the reference bots that reach a goal in the fewest ticks.
+
+use std::{path::PathBuf, process::ExitCode, time::Instant};
+
+use paperclips::{
+    PaperClips, Ticks,
+    rng::PCRng,
+    speedrun::{Goal, Search, record},
+    util::{ticks_to_duration, time_cruncher},
+};
+
+const USAGE: &str = "\
+Usage: speedrun [OPTIONS]
+
+Options:
+  -g, --goal <GOAL>          milestone:<N>, post-human, space, endgame or credits [default: milestone:6]
+  -t, --max-ticks <N>        Runs that haven't reached the goal by then are stopped [default: 3600000 (10 hours)]
+  -s, --seed <SEED>          Seed of the game and of the search [default: 0]
+  -n, --generations <N>      [default: 10]
+  -p, --population <N>       Policies tried every generation [default: 32]
+  -e, --elites <N>           Best policies kept as parents [default: 8]
+      --sigma <SIGMA>        How much the policies get mutated [default: 0.3]
+  -j, --threads <N>          [default: every core]
+  -l, --load <PATH>          Start from a save made by this game
+  -o, --replay <PATH>        Save the replay of the best run to PATH
+  -a, --actions              Print every action of the best run
+  -h, --help                 Print this message
+";
+
+struct Args {
+    search: Search,
+    seed: u64,
+    load: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    actions: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        search: Search::new(Goal::Milestone(6), 3_600_000),
+        seed: 0,
+        load: None,
+        replay: None,
+        actions: false,
+    };
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("missing value for `{arg}`"));
+        macro_rules! parse {
+            ($what:literal) => {{
+                let value = value()?;
+                value.replace('_', "").parse().map_err(|e| format!("invalid {} `{value}`: {e}", $what))?
+            }};
+        }
+        match arg.as_str() {
+            "-g" | "--goal" => args.search.goal = value()?.parse()?,
+            "-t" | "--max-ticks" => args.search.max_ticks = parse!("tick count"),
+            "-s" | "--seed" => args.seed = parse!("seed"),
+            "-n" | "--generations" => args.search.generations = parse!("generation count"),
+            "-p" | "--population" => args.search.population = parse!("population"),
+            "-e" | "--elites" => args.search.elites = parse!("elite count"),
+            "--sigma" => args.search.sigma = parse!("sigma"),
+            "-j" | "--threads" => args.search.threads = parse!("thread count"),
+            "-l" | "--load" => args.load = Some(value()?.into()),
+            "-o" | "--replay" => args.replay = Some(value()?.into()),
+            "-a" | "--actions" => args.actions = true,
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                std::process::exit(0);
+            },
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+    args.search.seed = args.seed;
+
+    if args.search.population == 0 || args.search.threads == 0 {
+        return Err("the population and the thread count must be at least 1".to_string());
+    }
+
+    Ok(args)
+}
+
+fn game_time(ticks: Ticks) -> String {
+    time_cruncher(ticks_to_duration(ticks)).trim_end().to_string()
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+    let mut initial = match &args.load {
+        Some(path) => PaperClips::load_from_file(path).map_err(|e| format!("couldn't load {}: {e}", path.display()))?,
+        None => PaperClips::default(),
+    };
+    initial.rng = PCRng::seeded(args.seed);
+    let search = args.search;
+
+    println!(
+        "Looking for the fastest way to {} with {} threads, {} policies over {} generations",
+        search.goal, search.threads, search.population, search.generations,
+    );
+    let start = Instant::now();
+    let best = search.run(&initial, |generation, best| {
+        match best.reached {
+            Some(ticks) => println!("Generation {generation:>3}: {ticks} ticks ({})", game_time(ticks)),
+            None => println!("Generation {generation:>3}: not reached, progress {:.2}", best.progress),
+        }
+    });
+    println!("Searched for {:.2?}", start.elapsed());
+    println!();
+
+    let Some(ticks) = best.reached else {
+        return Err(format!("no policy reached {} within {} ticks", search.goal, search.max_ticks));
+    };
+    println!("Best: {ticks} ticks ({})", game_time(ticks));
+    println!("Policy: {}", best.policy);
+
+    let replay = record(&initial, best.policy, search.goal, ticks);
+    println!("Actions: {}", replay.actions.len());
+    if args.actions {
+        // repeated actions are folded into one line
+        let mut actions = replay.actions.iter().peekable();
+        while let Some(&(first, action)) = actions.next() {
+            let (mut last, mut count) = (first, 1);
+            while let Some(&(ticks, _)) = actions.next_if(|(_, next)| *next == action) {
+                last = ticks;
+                count += 1;
+            }
+            match count {
+                1 => println!("[{first:>9}] {action}"),
+                _ => println!("[{first:>9}] {action} x{count} (until {last})"),
+            }
+        }
+    }
+    if let Some(path) = &args.replay {
+        replay.save_to_file(path).map_err(|e| format!("couldn't save {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            eprint!("\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Float, PaperClips, combat::{MAX_SHIP_CAP, ShipCount}, end::Dismantle, factory::{DRONE_AMOUNTS, POWER_AMOUNTS}, investments::Riskiness, project::{PROJECT_121, Project}, space::{PROBE_COST, ProbeStat}, strategy::strategies::Strat};
+
+/// Everything the player can do
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    /// # clipClick(1)
+    MakePaperclip,
+    LowerPrice,
+    RaisePrice,
+    /// # buyAds
+    BuyAds,
+    BuyWire,
+    ToggleWireBuyer,
+    /// # makeClipper
+    MakeClipper,
+    /// # makeMegaClipper
+    MakeMegaClipper,
+
+    AddProcessor,
+    AddMemory,
+    QuantumCompute,
+    BuyProject(&'static Project),
+
+    SetRiskiness(Riskiness),
+    InvestDeposit,
+    InvestWithdraw,
+    InvestUpgrade,
+
+    PickStrat(&'static Strat),
+    NewTourney,
+    RunTourney,
+
+    /// # makeFactory
+    MakeFactory,
+    /// # makeHarvester
+    MakeHarvester(u32),
+    /// # makeWireDrone
+    MakeWireDrone(u32),
+    FactoryReboot,
+    HarvesterReboot,
+    WireDroneReboot,
+    /// # makeFarm
+    MakeFarm(u32),
+    /// # makeBattery
+    MakeBattery(u32),
+    FarmReboot,
+    BatteryReboot,
+
+    /// # makeProbe
+    MakeProbe,
+    RaiseProbeStat(ProbeStat),
+    LowerProbeStat(ProbeStat),
+    /// # increaseProbeTrust
+    IncreaseProbeTrust,
+    /// # increaseMaxTrust
+    IncreaseMaxTrust,
+    /// How many ships a side can get in the next battles, see `Combat::ship_cap`
+    SetShipCap(ShipCount),
+
+    Cheat(Cheat),
+    /// Starts over, keeping the prestige
+    Reset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cheat {
+    Clips,
+    Money,
+    Trust,
+    Ops,
+    Creativity,
+    Yomi,
+    ResetPrestige,
+    DestroyAllHumans,
+    PrestigeU,
+    PrestigeS,
+    ZeroMatter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Funds,
+    /// # unusedClips
+    Clips,
+    Wire,
+    Trust,
+    Operations,
+    Yomi,
+    /// Probe trust not spent on the probe design yet
+    ProbeTrust,
+    Honor,
+    /// # swarmGifts
+    SwarmGifts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionError {
+    /// The part of the game the action belongs to isn't unlocked, or is already gone
+    Locked,
+    NotEnough { resource: Resource, needed: Float, available: Float },
+    /// The price can't go below $0.01
+    MinimumPrice,
+    /// The project isn't on screen (still locked, or already bought)
+    ProjectNotBuyable,
+    /// The project is on screen, but its cost can't be paid yet
+    ProjectNotAffordable,
+    /// The strat hasn't been bought
+    StratNotAvailable,
+    TournamentInProgress,
+    /// There is no tournament waiting to be run
+    NoTournament,
+    /// The probe stat is already at 0
+    ProbeStatAtZero,
+    /// Probe trust can't go over the max trust
+    MaxTrust,
+    /// Only the amounts the buttons offer can be bought at once
+    UnsupportedAmount(u32),
+}
+
+/// Like `Debug`, but projects and strats only show their name
+impl Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::BuyProject(project) => write!(f, "BuyProject({})", project.name),
+            Action::PickStrat(strat) => write!(f, "PickStrat({})", strat.name),
+            action => write!(f, "{action:?}"),
+        }
+    }
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Resource::Funds => "funds",
+            Resource::Clips => "unused clips",
+            Resource::Wire => "wire",
+            Resource::Trust => "trust",
+            Resource::Operations => "operations",
+            Resource::Yomi => "yomi",
+            Resource::ProbeTrust => "probe trust",
+            Resource::Honor => "honor",
+            Resource::SwarmGifts => "swarm gifts",
+        })
+    }
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Locked => write!(f, "not available right now"),
+            ActionError::NotEnough { resource, needed, available } => write!(f, "not enough {resource} ({available:.2} of {needed:.2})"),
+            ActionError::MinimumPrice => write!(f, "the price is already at its minimum"),
+            ActionError::ProjectNotBuyable => write!(f, "the project can't be bought"),
+            ActionError::ProjectNotAffordable => write!(f, "the project's cost can't be paid yet"),
+            ActionError::StratNotAvailable => write!(f, "the strat hasn't been unlocked"),
+            ActionError::TournamentInProgress => write!(f, "a tournament is already in progress"),
+            ActionError::NoTournament => write!(f, "there's no tournament to run"),
+            ActionError::ProbeStatAtZero => write!(f, "the probe stat is already at 0"),
+            ActionError::MaxTrust => write!(f, "probe trust is already at its max"),
+            ActionError::UnsupportedAmount(amount) => write!(f, "can't buy {amount} at once"),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+fn unlocked(flag: bool) -> Result<(), ActionError> {
+    if flag { Ok(()) } else { Err(ActionError::Locked) }
+}
+
+fn supported(amount: u32, amounts: &[u32]) -> Result<(), ActionError> {
+    if amounts.contains(&amount) { Ok(()) } else { Err(ActionError::UnsupportedAmount(amount)) }
+}
+
+fn enough(resource: Resource, needed: Float, available: Float) -> Result<(), ActionError> {
+    if available >= needed {
+        Ok(())
+    } else {
+        Err(ActionError::NotEnough { resource, needed, available })
+    }
+}
+
+impl PaperClips {
+    /// Checks if `action` can be applied right now, without changing anything
+    pub fn check(&self, action: Action) -> Result<(), ActionError> {
+        use Resource::*;
+
+        let funds = self.business.funds;
+        let clips = self.business.unused_clips;
+        match action {
+            Action::MakePaperclip => enough(Wire, 1.0, self.wire.count),
+            Action::LowerPrice => {
+                unlocked(self.human_flag())?;
+                if self.business.margin <= 0.01 { Err(ActionError::MinimumPrice) } else { Ok(()) }
+            },
+            Action::RaisePrice => unlocked(self.human_flag()),
+            Action::BuyAds => {
+                unlocked(self.human_flag())?;
+                enough(Funds, self.business.ad_cost, funds)
+            },
+            Action::BuyWire => {
+                unlocked(self.human_flag())?;
+                enough(Funds, self.wire.cost, funds)
+            },
+            Action::ToggleWireBuyer => unlocked(self.human_flag() && self.wire.buyer_flag),
+            Action::MakeClipper => {
+                unlocked(self.human_flag() && self.business.clipper_flag)?;
+                enough(Funds, self.business.clipper_cost, funds)
+            },
+            Action::MakeMegaClipper => {
+                unlocked(self.human_flag() && self.business.mega_clipper_flag)?;
+                enough(Funds, self.business.mega_clipper_cost, funds)
+            },
+
+            Action::AddProcessor | Action::AddMemory => {
+                unlocked(self.computational.comp_flag && self.end.dismantle < Dismantle::Processors)?;
+                if !self.human_flag() {
+                    return enough(SwarmGifts, 1.0, self.factory.swarm_gifts)
+                }
+                let c = &self.computational;
+                let used = (c.processors + c.memory) as Float;
+                enough(Trust, used + 1.0, c.trust as Float)
+            },
+            Action::QuantumCompute => unlocked(self.qchips.q_flag && self.end.dismantle < Dismantle::Quantum),
+            Action::BuyProject(project) => {
+                if !self.projects.buyable_projects.iter().any(|&(_, p)| p == project) {
+                    Err(ActionError::ProjectNotBuyable)
+                } else if !(project.cost.1)(self) {
+                    Err(ActionError::ProjectNotAffordable)
+                } else {
+                    Ok(())
+                }
+            },
+
+            Action::SetRiskiness(_) | Action::InvestWithdraw => unlocked(self.investments.engine_flag),
+            Action::InvestDeposit => {
+                unlocked(self.investments.engine_flag)?;
+                enough(Funds, 1.0, funds)
+            },
+            Action::InvestUpgrade => {
+                unlocked(self.investments.engine_flag)?;
+                enough(Yomi, self.investments.invest_upgrade_cost, self.strategy.yomi)
+            },
+
+            Action::PickStrat(strat) => {
+                unlocked(self.strategy.engine_flag)?;
+                if !self.strategy.strats.iter().any(|&(s, _)| s == strat) {
+                    return Err(ActionError::StratNotAvailable)
+                }
+                match (self.strategy.tourney_in_prog, self.strategy.disable_run_button) {
+                    (false, _) => Err(ActionError::NoTournament),
+                    // the rounds are being played
+                    (true, true) => Err(ActionError::TournamentInProgress),
+                    (true, false) => Ok(()),
+                }
+            },
+            Action::NewTourney => {
+                unlocked(self.strategy.engine_flag)?;
+                if self.strategy.tourney_in_prog {
+                    return Err(ActionError::TournamentInProgress)
+                }
+                enough(Operations, self.strategy.tourney_cost, self.computational.operations)
+            },
+            Action::RunTourney => {
+                unlocked(self.strategy.engine_flag)?;
+                if self.strategy.tourney_in_prog && !self.strategy.disable_run_button {
+                    Ok(())
+                } else {
+                    Err(ActionError::NoTournament)
+                }
+            },
+
+            Action::MakeFactory => {
+                unlocked(self.factory.factory_flag && !self.space_flag())?;
+                enough(Clips, self.factory.factory_cost, clips)
+            },
+            Action::MakeHarvester(amount) => {
+                unlocked(self.factory.harvester_flag && !self.space_flag())?;
+                supported(amount, &DRONE_AMOUNTS)?;
+                enough(Clips, self.factory.harvester_price(amount), clips)
+            },
+            Action::MakeWireDrone(amount) => {
+                unlocked(self.factory.wire_drone_flag && !self.space_flag())?;
+                supported(amount, &DRONE_AMOUNTS)?;
+                enough(Clips, self.factory.wire_drone_price(amount), clips)
+            },
+            Action::FactoryReboot => unlocked(self.factory.factory_flag),
+            Action::HarvesterReboot => unlocked(self.factory.harvester_flag),
+            Action::WireDroneReboot => unlocked(self.factory.wire_drone_flag),
+            Action::MakeFarm(amount) => {
+                unlocked(self.power_grid_flag())?;
+                supported(amount, &POWER_AMOUNTS)?;
+                enough(Clips, self.factory.farm_price(amount), clips)
+            },
+            Action::MakeBattery(amount) => {
+                unlocked(self.power_grid_flag())?;
+                supported(amount, &POWER_AMOUNTS)?;
+                enough(Clips, self.factory.battery_price(amount), clips)
+            },
+            Action::FarmReboot | Action::BatteryReboot => unlocked(self.power_grid_flag()),
+
+            Action::MakeProbe => {
+                unlocked(self.space_flag())?;
+                enough(Clips, PROBE_COST, clips)
+            },
+            Action::RaiseProbeStat(_) => {
+                unlocked(self.space_flag())?;
+                let s = &self.space;
+                enough(ProbeTrust, s.probe_used_trust + 1.0, s.probe_trust)
+            },
+            Action::LowerProbeStat(stat) => {
+                unlocked(self.space_flag())?;
+                if self.space.probe_stat(stat) <= 0.0 { Err(ActionError::ProbeStatAtZero) } else { Ok(()) }
+            },
+            Action::IncreaseProbeTrust => {
+                unlocked(self.space_flag())?;
+                if self.space.probe_trust >= self.space.max_trust {
+                    return Err(ActionError::MaxTrust)
+                }
+                enough(Yomi, self.space.probe_trust_cost, self.strategy.yomi)
+            },
+            Action::IncreaseMaxTrust => {
+                unlocked(self.space_flag() && self.projects.is_active(PROJECT_121))?;
+                enough(Honor, self.space.max_trust_cost, self.combat.honor as Float)
+            },
+
+            Action::SetShipCap(_) | Action::Cheat(_) | Action::Reset => Ok(()),
+        }
+    }
+
+    /// Applies `action` if it passes [`PaperClips::check`], otherwise nothing changes
+    pub fn apply(&mut self, action: Action) -> Result<(), ActionError> {
+        self.check(action)?;
+
+        match action {
+            Action::MakePaperclip => self.clip_click(1.0),
+            Action::LowerPrice => self.business.lower_price(),
+            Action::RaisePrice => self.business.raise_price(),
+            Action::BuyAds => self.business.buy_ads(),
+            Action::BuyWire => self.buy_wire(),
+            Action::ToggleWireBuyer => self.wire.buyer_status ^= true,
+            Action::MakeClipper => self.business.make_clipper(),
+            Action::MakeMegaClipper => self.business.make_mega_clipper(),
+
+            Action::AddProcessor => self.add_processors(),
+            Action::AddMemory => self.add_memory(),
+            Action::QuantumCompute => self.quantum_compute(),
+            Action::BuyProject(project) => {
+                if let Some(bpi) = self.projects.buyable_projects.iter().position(|&(_, p)| p == project) {
+                    self.buy_project(bpi);
+                }
+            },
+
+            Action::SetRiskiness(riskiness) => self.investments.riskiness = riskiness,
+            Action::InvestDeposit => self.invest_deposit(),
+            Action::InvestWithdraw => self.invest_withdraw(),
+            Action::InvestUpgrade => self.invest_upgrade(),
+
+            Action::PickStrat(strat) => self.strategy.pick = strat,
+            Action::NewTourney => self.new_tourney(),
+            Action::RunTourney => self.run_tourney(),
+
+            Action::MakeFactory => self.make_factory(),
+            Action::MakeHarvester(amount) => self.make_harvester(amount),
+            Action::MakeWireDrone(amount) => self.make_wire_drone(amount),
+            Action::FactoryReboot => self.factory_reboot(),
+            Action::HarvesterReboot => self.harvester_reboot(),
+            Action::WireDroneReboot => self.wire_drone_reboot(),
+            Action::MakeFarm(amount) => self.make_farm(amount),
+            Action::MakeBattery(amount) => self.make_battery(amount),
+            Action::FarmReboot => self.farm_reboot(),
+            Action::BatteryReboot => self.battery_reboot(),
+
+            Action::MakeProbe => self.make_probe(),
+            Action::RaiseProbeStat(stat) => self.space.raise_probe_stat(stat),
+            Action::LowerProbeStat(stat) => self.space.lower_probe_stat(stat),
+            Action::IncreaseProbeTrust => self.increase_probe_trust(),
+            Action::IncreaseMaxTrust => self.increase_max_trust(),
+            Action::SetShipCap(cap) => self.combat.ship_cap = cap.clamp(1, MAX_SHIP_CAP),
+
+            Action::Cheat(cheat) => match cheat {
+                Cheat::Clips => self.cheat_clips(),
+                Cheat::Money => self.cheat_money(),
+                Cheat::Trust => self.cheat_trust(),
+                Cheat::Ops => self.cheat_ops(),
+                Cheat::Creativity => self.cheat_creat(),
+                Cheat::Yomi => self.cheat_yomi(),
+                Cheat::ResetPrestige => self.reset_prestige(),
+                Cheat::DestroyAllHumans => self.space.hypno_drone_event = Some(self.ticks),
+                Cheat::PrestigeU => self.cheat_prestige_u(),
+                Cheat::PrestigeS => self.cheat_prestige_s(),
+                Cheat::ZeroMatter => self.zero_matter(),
+            },
+            Action::Reset => self.reset(),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use std::{fmt::{self, Display, Write}, sync::atomic::{AtomicUsize, Ordering}, thread};
+
+use crate::{
+    Float, PaperClips, Ticks,
+    event::GameEvent,
+    player::Team,
+    rng::PCRng,
+    speedrun::Goal,
+    console::format_game_time,
+    util::ticks_to_duration,
+};
+
+/// Many independent games from the same start, each with its own seed
+#[derive(Debug, Clone, Copy)]
+pub struct Batch {
+    pub games: usize,
+    /// Games stop once it's reached, or after `max_ticks`
+    pub goal: Option<Goal>,
+    pub max_ticks: Ticks,
+    /// Game `i` gets seeded with `seed + i`
+    pub seed: u64,
+    pub threads: usize,
+    /// Let the reference bots play, without them nothing gets made
+    pub bots: bool,
+}
+
+impl Batch {
+    pub fn new(games: usize, max_ticks: Ticks) -> Self {
+        Self {
+            games,
+            goal: None,
+            max_ticks,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            bots: true,
+        }
+    }
+
+    /// Plays one game of the batch
+    pub fn play(&self, initial: &PaperClips, game: usize) -> GameResult {
+        let seed = self.seed.wrapping_add(game as u64);
+        let mut pc = initial.clone();
+        pc.rng = PCRng::seeded(seed);
+        let mut team = match self.bots {
+            true => Team::reference(),
+            false => Team::default(),
+        };
+
+        let mut result = GameResult { seed, ..Default::default() };
+        let end = initial.ticks + self.max_ticks;
+        while pc.ticks < end && !self.goal.is_some_and(|goal| goal.reached(&pc)) {
+            pc.step_with(&mut team);
+            for (ticks, event) in pc.take_events() {
+                match event {
+                    GameEvent::MilestoneReached(milestone) => result.milestones.push((milestone, ticks - initial.ticks)),
+                    GameEvent::BattleWon { .. } => result.battles_won += 1,
+                    GameEvent::BattleLost { .. } => result.battles_lost += 1,
+                    _ => {},
+                }
+            }
+        }
+
+        result.ticks = pc.ticks - initial.ticks;
+        result.reached = self.goal.is_some_and(|goal| goal.reached(&pc));
+        result.clips = pc.business.clips;
+        result.yomi = pc.strategy.yomi;
+        result.investments = pc.investments.ledger + pc.investments.port_total();
+        result.honor = pc.combat.honor as Float;
+        result
+    }
+
+    /// Plays every game on all the threads, `on_game` gets called after each one with how many are done
+    pub fn run(&self, initial: &PaperClips, on_game: impl Fn(usize) + Sync) -> BatchReport {
+        let next = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+        let results = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.threads.clamp(1, self.games.max(1)))
+                .map(|_| scope.spawn(|| {
+                    let mut results = Vec::new();
+                    // games take very different times, so they're handed out one by one
+                    loop {
+                        let game = next.fetch_add(1, Ordering::Relaxed);
+                        if game >= self.games {
+                            break results
+                        }
+                        results.push((game, self.play(initial, game)));
+                        on_game(done.fetch_add(1, Ordering::Relaxed) + 1);
+                    }
+                }))
+                .collect();
+            let mut results: Vec<_> = handles.into_iter().flat_map(|handle| handle.join().expect("game thread panicked")).collect();
+            results.sort_by_key(|(game, _)| *game);
+            results.into_iter().map(|(_, result)| result).collect()
+        });
+        BatchReport { goal: self.goal, results }
+    }
+}
+
+/// How one game of a [`Batch`] ended
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameResult {
+    pub seed: u64,
+    /// How long the game ran
+    pub ticks: Ticks,
+    /// If it stopped on the goal
+    pub reached: bool,
+    /// Every milestone reached and the tick it got reached on, in order, see `milestone_check`.
+    /// Games loaded from a save start after the milestones they already have
+    pub milestones: Vec<(u8, Ticks)>,
+    pub clips: Float,
+    pub yomi: Float,
+    pub battles_won: u32,
+    pub battles_lost: u32,
+    /// What the investments made or lost, the ledger plus what's still invested
+    pub investments: Float,
+    pub honor: Float,
+}
+
+/// Summary statistics of a sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: Float,
+    pub std_dev: Float,
+    pub min: Float,
+    pub p10: Float,
+    pub median: Float,
+    pub p90: Float,
+    pub max: Float,
+}
+
+impl Distribution {
+    /// `None` for an empty sample
+    pub fn new(mut values: Vec<Float>) -> Option<Self> {
+        if values.is_empty() {
+            return None
+        }
+        values.sort_by(Float::total_cmp);
+        let count = values.len();
+        let mean = values.iter().sum::<Float>() / count as Float;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<Float>() / count as Float;
+        // nearest rank
+        let percentile = |p: Float| values[((p * count as Float).ceil() as usize).clamp(1, count) - 1];
+        Some(Self {
+            count,
+            mean,
+            std_dev: variance.sqrt(),
+            min: values[0],
+            p10: percentile(0.1),
+            median: percentile(0.5),
+            p90: percentile(0.9),
+            max: values[count - 1],
+        })
+    }
+}
+
+/// Every result of a [`Batch`], in game order
+#[derive(Debug, Clone)]
+pub struct BatchReport {
+    pub goal: Option<Goal>,
+    pub results: Vec<GameResult>,
+}
+
+impl BatchReport {
+    /// Ticks to reach `milestone`, among the games that reached it
+    pub fn milestone(&self, milestone: u8) -> Option<Distribution> {
+        Distribution::new(
+            self.results.iter()
+                .filter_map(|result| result.milestones.iter().find(|&&(reached, _)| reached == milestone))
+                .map(|&(_, ticks)| ticks as Float)
+                .collect()
+        )
+    }
+    /// Every milestone reached by at least one game, in order
+    pub fn milestones(&self) -> Vec<u8> {
+        let mut milestones: Vec<_> = self.results.iter().flat_map(|result| result.milestones.iter().map(|&(milestone, _)| milestone)).collect();
+        milestones.sort_unstable();
+        milestones.dedup();
+        milestones
+    }
+    pub fn distribution(&self, value: impl Fn(&GameResult) -> Float) -> Option<Distribution> {
+        Distribution::new(self.results.iter().map(value).collect())
+    }
+    /// One line per game
+    pub fn to_csv(&self) -> String {
+        let milestones = self.milestones();
+        let mut s = String::from("seed,ticks,reached,clips,yomi,battles_won,battles_lost,investments,honor");
+        for milestone in &milestones {
+            let _ = write!(s, ",milestone_{milestone}");
+        }
+        s.push('\n');
+        for r in &self.results {
+            let _ = write!(s, "{},{},{},{},{},{},{},{},{}", r.seed, r.ticks, r.reached, r.clips, r.yomi, r.battles_won, r.battles_lost, r.investments, r.honor);
+            for &milestone in &milestones {
+                s.push(',');
+                if let Some((_, ticks)) = r.milestones.iter().find(|&&(reached, _)| reached == milestone) {
+                    let _ = write!(s, "{ticks}");
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// A table of the milestone times and the final values
+impl Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let games = self.results.len();
+        if let Some(goal) = self.goal {
+            let reached = self.results.iter().filter(|result| result.reached).count();
+            writeln!(f, "{goal} reached in {reached}/{games} games")?;
+        }
+
+        let time = |ticks: Float| format_game_time(ticks_to_duration(ticks as Ticks));
+        writeln!(f, "{:<14} {:>9} {:>14} {:>14} {:>14} {:>14} {:>14}", "Time to", "games", "min", "p10", "median", "p90", "max")?;
+        for milestone in self.milestones() {
+            let Some(d) = self.milestone(milestone) else { continue };
+            writeln!(
+                f, "{:<14} {:>9} {:>14} {:>14} {:>14} {:>14} {:>14}",
+                format!("milestone {milestone}"), format!("{}/{games}", d.count), time(d.min), time(d.p10), time(d.median), time(d.p90), time(d.max),
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:<14} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14}", "Final", "mean", "std dev", "min", "p10", "median", "p90", "max")?;
+        type Value = fn(&GameResult) -> Float;
+        let values: [(&str, Value); 6] = [
+            ("clips", |r| r.clips),
+            ("yomi", |r| r.yomi),
+            ("battles won", |r| r.battles_won as Float),
+            ("battles lost", |r| r.battles_lost as Float),
+            ("investments", |r| r.investments),
+            ("honor", |r| r.honor),
+        ];
+        // huge numbers get an exponent, like in the telemetry
+        let number = |value: Float| match value.abs() < 1e12 {
+            true => format!("{value:.2}"),
+            false => format!("{value:.3e}"),
+        };
+        for (name, value) in values {
+            let Some(d) = self.distribution(value) else { continue };
+            write!(f, "{name:<14}")?;
+            for value in [d.mean, d.std_dev, d.min, d.p10, d.median, d.p90, d.max] {
+                write!(f, " {:>14}", number(value))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -2,9 +2,10 @@ use std::collections::VecDeque;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{util::{floor_to, round_to}, Float, PaperClips};
+use crate::{util::{floor_to, round_to}, end::Dismantle, Float, PaperClips};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Business {
     /// # funds
     pub funds: Float,
@@ -135,13 +136,12 @@ impl PaperClips {
         self.business.unused_clips += amount;
     }
     pub fn clip_click(&mut self, amount: Float) {
-        // if dismantle >= 4 {
-        //     final_clips += 1;
-        // }
-
         if self.wire.count >= 1.0 {
             let amount = amount.min(self.wire.count);
 
+            if self.end.dismantle >= Dismantle::Strategy {
+                self.end.final_clips += amount;
+            }
             self.create_clips(amount);
             self.wire.count -= amount;
         }
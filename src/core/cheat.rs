@@ -5,28 +5,28 @@ impl PaperClips {
         const ADDED_CLIPS: Float = 100000000.0;
         self.business.clips += ADDED_CLIPS;
         self.business.unused_clips += ADDED_CLIPS;
-        self.console.push("you just cheated");
+        self.log("you just cheated");
     }
     pub fn cheat_money(&mut self) {
         self.business.funds += 10000000.0;
-        self.console.push("LIZA just cheated");
+        self.log("LIZA just cheated");
     }
     pub fn cheat_trust(&mut self) {
         self.computational.trust += 1;
-        self.console.push("Hilary is nice. Also, Liza just cheated");
+        self.log("Hilary is nice. Also, Liza just cheated");
     }
     pub fn cheat_ops(&mut self) {
         self.computational.standard_ops += 10000.0;
-        self.console.push("you just cheated, Liza");
+        self.log("you just cheated, Liza");
     }
     pub fn cheat_creat(&mut self) {
         self.computational.creativity_flag = true;
         self.computational.creativity += 1000.0;
-        self.console.push("Liza just cheated. Very creative!");
+        self.log("Liza just cheated. Very creative!");
     }
     pub fn cheat_yomi(&mut self) {
         self.strategy.yomi += 1000000.0;
-        self.console.push("you just cheated");
+        self.log("you just cheated");
     }
 
     // // This is GUI-only. It has no effect on the core state
@@ -43,6 +43,6 @@ impl PaperClips {
 
     pub fn zero_matter(&mut self) {
         self.space.available_matter = 0.0;
-        self.console.push("you just cheated");
+        self.log("you just cheated");
     }
 }
@@ -1,11 +1,11 @@
-use std::array;
-
 use arrayvec::ArrayString;
+use serde::{Deserialize, Serialize};
 
-use crate::{Float, PaperClips, combat::{battle_name::{BATTLE_NAMES, BattleName, BattleNumbers, max_battlename_len}, ship::{Ship, Status, Team}}, project::{PROJECT_121, PROJECT_134}, rng::PCRng};
+use crate::{Float, PaperClips, combat::{battle_name::{BATTLE_NAMES, BattleName, BattleNumbers, max_battlename_len}, history::{BattleRecord, Winner}, ship::{Ship, Status, Team}}, event::GameEvent, project::{PROJECT_121, PROJECT_134}, rng::PCRng};
 
 pub mod ship;
 pub mod battle_name;
+pub mod history;
 
 /// # battleGRID_WIDTH
 pub const BATTLE_WIDTH: usize = 310;
@@ -17,8 +17,11 @@ pub const GRID_WIDTH: usize = BATTLE_WIDTH / GRID_SCALE; // 31
 pub const GRID_HEIGHT: usize = BATTLE_HEIGHT / GRID_SCALE; // 15
 
 pub const GRID_SCALE: usize = 10;
+pub const GRID_CELLS: usize = GRID_WIDTH * GRID_HEIGHT;
 pub const PROBE_COMBAT_BASE_RATE: Float = 0.15;
 pub const DEATH_THRESHOLD: Float = 0.5;
+/// How many frames a dead ship stays on the field, fading out
+pub const DEATH_FRAMES: u8 = 10;
 
 pub const DEFAULT_BATTLENAME: &str = "Durenstein 1";
 pub const THRENODY_START: &str = "Threnody for the Heroes of ";
@@ -26,13 +29,17 @@ pub const THRENODY_START: &str = "Threnody for the Heroes of ";
 pub const MAX_BATTLENAME_LEN: usize = max_battlename_len()+1 + 1+BattleID::MAX.ilog10() as usize;
 pub const MAX_THRENODY_LEN: usize = THRENODY_START.len() + MAX_BATTLENAME_LEN;
 pub const WAR_TRIGGER: Float = 1000000.0;
+/// Most ships a side can get, the original's 200
+pub const DEFAULT_SHIP_CAP: ShipCount = 200;
+/// 50 times the original, every snapshot of the simulation gets a copy of the ships
+pub const MAX_SHIP_CAP: ShipCount = 10_000;
 
 pub type BattleID = u32;
-pub type Grid = [[Cell; GRID_HEIGHT]; GRID_WIDTH];
-pub type ShipCount = u8;
+pub type ShipCount = u32;
 pub type Honor = u64;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Combat {
     pub battle_flag: bool,
 
@@ -47,6 +54,13 @@ pub struct Combat {
     pub ship_count: (ShipCount, ShipCount),
     /// # (battleLEFTSHIPS, battleRIGHTSHIPS)
     pub max_ships: (ShipCount, ShipCount),
+    /// What `max_ships` can get to, up to [`MAX_SHIP_CAP`]
+    pub ship_cap: ShipCount,
+    /// What each side of the current battle is worth, the fleets the original would have drawn.
+    /// They don't depend on `ship_cap`, which only changes how many ships show up
+    pub battle_honor: (Honor, Honor),
+    #[serde(skip)]
+    pub grid: Grid,
     pub attack_speed_flag: bool,
     pub unit_size: Float,
 
@@ -62,10 +76,14 @@ pub struct Combat {
     pub bonus_honor: Honor,
 
     pub battle_name: BattleName,
+    #[serde(with = "crate::save::padded_array")]
     pub battle_numbers: BattleNumbers,
     /// # threnodyTitle
     pub threnody_title: ArrayString<MAX_BATTLENAME_LEN>,
     pub threnody_project: ArrayString<MAX_BATTLENAME_LEN>,
+
+    /// Every battle so far, in order
+    pub history: Vec<BattleRecord>,
 }
 
 impl Default for Combat {
@@ -79,7 +97,10 @@ impl Default for Combat {
 
             ships: Vec::new(),
             ship_count: (0, 0),
-            max_ships: (200, 200),
+            max_ships: (DEFAULT_SHIP_CAP, DEFAULT_SHIP_CAP),
+            ship_cap: DEFAULT_SHIP_CAP,
+            battle_honor: (DEFAULT_SHIP_CAP as Honor, DEFAULT_SHIP_CAP as Honor),
+            grid: Grid::default(),
             attack_speed_flag: false,
             unit_size: 0.0,
 
@@ -98,27 +119,35 @@ impl Default for Combat {
             threnody_title: ArrayString::from(DEFAULT_BATTLENAME).expect("Always valid"),
             threnody_project: ArrayString::from(DEFAULT_BATTLENAME).expect("Always valid"),
 
+            history: Vec::new(),
+
         }
     }
 }
 
 impl PaperClips {
     pub fn update_combat(&mut self) {
+        if !self.combat.battles { return }
         // clear_frame(); // handled by GUI
-        let mut grid = self.combat.create_grid();
-        self.combat.move_ships(&grid);
-        self.do_combat(&mut grid);
+        self.combat.update_grid();
+        self.combat.move_ships();
+        self.do_combat();
+        // after the combat, so the grid's indices stay valid
+        self.combat.ships.retain(|s| !matches!(s.status, Status::Dead(frames) if frames >= DEATH_FRAMES));
+
+        if self.combat.ship_count.0 == 0 || self.combat.ship_count.1 == 0 {
+            self.combat.battle_end_delay = self.combat.battle_end_delay.saturating_add(1);
+        }
+        self.check_for_battle_end();
     }
     #[inline]
     pub fn war(&mut self) {
         self.check_for_battles();
     }
     pub fn check_for_battles(&mut self) {
-        if self.space.drifter_count > WAR_TRIGGER && self.space.probe_count > 0.0 && !self.combat.battles {
-            if self.rng.random_bool(0.5, true) {
-                self.combat.battle_flag = true;
-                self.create_battle();
-            }
+        if self.space.drifter_count > WAR_TRIGGER && self.space.probe_count > 0.0 && !self.combat.battles && self.rng.random_bool(0.5, true) {
+            self.combat.battle_flag = true;
+            self.create_battle();
         }
     }
     pub fn create_battle(&mut self) {
@@ -132,22 +161,34 @@ impl PaperClips {
         let ss = (rng.random_float(true) * space.probe_count).max(1.0);
         // let tt = rng.random_float(true) * space.available_matter;
 
+        let cap = combat.ship_cap.clamp(1, MAX_SHIP_CAP) as Float;
+        let original_cap = DEFAULT_SHIP_CAP as Float;
+        let fleets = ((ss / 1000000.0).ceil(), (rr / 1000000.0).ceil());
         // battleLEFTSHIPS
-        combat.max_ships.0 = (ss / 1000000.0).ceil().min(200.0) as u8;
-        if combat.max_ships.0 >= 200 {
-            if rng.random_bool(0.5, false) {
-                combat.max_ships.0 = (rng.random_float(true) * 175.0).ceil() as ShipCount;
-            }
+        combat.max_ships.0 = fleets.0.min(cap) as ShipCount;
+        combat.battle_honor.0 = fleets.0.min(original_cap) as Honor;
+        if fleets.0 >= original_cap && rng.random_bool(0.5, false) {
+            // 175 out of 200 in the original
+            let share = rng.random_float(true) * 0.875;
+            combat.max_ships.0 = (share * fleets.0.min(cap)).ceil() as ShipCount;
+            combat.battle_honor.0 = (share * original_cap).ceil() as Honor;
         }
         // battleRIGHTSHIPS
-        combat.max_ships.1 = (rr / 1000000.0).ceil().min(200.0) as u8;
+        combat.max_ships.1 = fleets.1.min(cap) as ShipCount;
+        combat.battle_honor.1 = fleets.1.min(original_cap) as Honor;
 
         combat.update_battle_name(rng);
+        combat.battle_restart(rng);
+        combat.battles = true;
+        combat.history.push(BattleRecord::new(combat.battle_name, self.ticks, combat.max_ships, combat.unit_size));
     }
     pub fn check_for_battle_end(&mut self) {
         let combat = &mut self.combat;
+        let mut event = None;
 
         if !combat.battles { return }
+        // counted first, so an ended battle doesn't leave it at 1 and its record has every frame
+        combat.master_battle_clock += 1;
 
         if combat.ship_count.0 == 0 || combat.ship_count.1 == 0 {
             if self.projects.is_active(PROJECT_121) {
@@ -155,8 +196,14 @@ impl PaperClips {
                     (0, _) => { // LOST
                         if !combat.honor_count {
                             combat.bonus_honor = 0;
-                            combat.honor -= combat.max_ships.0 as Honor;
+                            // honor doesn't go negative
+                            let lost = combat.honor.min(combat.battle_honor.0);
+                            combat.honor -= lost;
+                            if let Some(record) = combat.history.last_mut() {
+                                record.honor_change = -(lost as i64);
+                            }
                             combat.honor_count = true;
+                            event = Some(GameEvent::BattleLost { honor: lost });
                         }
                         if let BattleName::Name(name) = combat.battle_name {
                             combat.threnody_title = name;
@@ -164,8 +211,12 @@ impl PaperClips {
                     }
                     (_, 0) => { // WIN
                         if !combat.honor_count {
-                            let honor_reward = combat.max_ships.1 as Honor + combat.bonus_honor;
+                            let honor_reward = combat.battle_honor.1 + combat.bonus_honor;
                             combat.honor += honor_reward;
+                            if let Some(record) = combat.history.last_mut() {
+                                record.honor_change = honor_reward as i64;
+                            }
+                            event = Some(GameEvent::BattleWon { honor: honor_reward });
                         }
                         if self.projects.is_active(PROJECT_134) {
                             combat.bonus_honor += 10;
@@ -182,47 +233,39 @@ impl PaperClips {
             }
         }
 
-        if combat.battle_end_delay >= combat.battle_end_timer {
+        if combat.battle_end_delay >= combat.battle_end_timer || combat.master_battle_clock >= 8000 {
             combat.end_battle();
         }
 
-        combat.master_battle_clock += 1;
-        if combat.master_battle_clock >= 8000 {
-            combat.end_battle();
+        if let Some(event) = event {
+            self.emit(event);
         }
     }
-    pub fn do_combat(&mut self, grid: &mut Grid) {
+    pub fn do_combat(&mut self) {
         let space = &mut self.space;
-        let combat = &mut self.combat;
+        let Combat { grid, ships, ship_count, unit_size, attack_speed_flag, history, .. } = &mut self.combat;
+        let mut losses = (0.0, 0.0);
 
         let px = space.probe_count * PROBE_COMBAT_BASE_RATE;
         let dx = space.drifter_count;
 
-        let ooda = if combat.attack_speed_flag { space.probe_speed * 0.2 } else { 0.0 };
-
-        for x in 0..GRID_WIDTH {
-            for y in 0..GRID_HEIGHT {
-                let cell = &mut grid[x][y];
+        let ooda = if *attack_speed_flag { space.probe_speed * 0.2 } else { 0.0 };
 
+        for gx in 0..GRID_WIDTH {
+            for gy in 0..GRID_HEIGHT {
+                let cell = grid.cell(gx, gy);
                 // First Check if there are enough ships in this cell to do combat
-                if cell.ships.len() < 2 { continue }
-
-                let mut teams = (0, 0);
+                if cell.len() < 2 { continue }
 
-                // Now count how many ships for each team in this cell;
-                for s in cell.ships.iter().filter(|s| s.status == Status::Alive) {
-                    let team = match s.team {
-                        Team::Left => &mut teams.0,
-                        Team::Right => &mut teams.1,
-                    };
-                    *team += 1;
-                }
+                // the grid already counted how many ships for each team are in this cell
+                let mut teams = (grid.flock(gx, gy, Team::Left).count, grid.flock(gx, gy, Team::Right).count);
                 if teams.0 == 0 || teams.1 == 0 { continue }
 
                 // now we have at least one ship of each team in this cell. 
                 // roll a weighted die to see if each ship gets killed
 
-                for s in &mut cell.ships {
+                for &i in cell {
+                    let s = &mut ships[i as usize];
                     let (dice_roll, death_threshold) = match s.team {
                         Team::Left => (
                             self.rng.random_float(true) * dx
@@ -238,25 +281,31 @@ impl PaperClips {
 
                     if dice_roll > death_threshold {
                         s.status = Status::Dead(0);
-                        let (count, collector) = match s.team {
+                        let (count, collector, loss) = match s.team {
                             Team::Left => {
                                 teams.0 -= 1;
-                                combat.ship_count.0 -= 1;
-                                (&mut space.probe_count, &mut space.probes_lost_combat)
+                                ship_count.0 -= 1;
+                                (&mut space.probe_count, &mut space.probes_lost_combat, &mut losses.0)
                             }
                             Team::Right => {
                                 teams.1 -= 1;
-                                combat.ship_count.1 -= 1;
-                                (&mut space.drifter_count, &mut space.drifters_killed)
+                                ship_count.1 -= 1;
+                                (&mut space.drifter_count, &mut space.drifters_killed, &mut losses.1)
                             }
                         };
-                        combat.unit_size = combat.unit_size.min(*count);
-                        *count -= combat.unit_size;
-                        *collector += combat.unit_size;
+                        *unit_size = unit_size.min(*count);
+                        *count -= *unit_size;
+                        *collector += *unit_size;
+                        *loss += *unit_size;
                     }
                 }
             }
         }
+
+        if let Some(record) = history.last_mut() {
+            record.probes_lost += losses.0;
+            record.drifters_killed += losses.1;
+        }
     }
 }
 
@@ -278,8 +327,15 @@ impl Combat {
         centroid.y = (centroid.y * 0.8) + (BATTLE_HEIGHT as Float / 2.0 * 0.2);
         centroid
     }
-    #[inline]
     pub fn end_battle(&mut self) {
+        if self.battles && let Some(record) = self.history.last_mut() {
+            record.duration = self.master_battle_clock;
+            record.winner = Some(match self.ship_count {
+                (0, _) => Winner::Drifters,
+                (_, 0) => Winner::Probes,
+                _ => Winner::Draw,
+            });
+        }
         self.honor_count = false;
         self.battle_clock = 0;
         self.master_battle_clock = 0;
@@ -310,34 +366,23 @@ impl Combat {
         }
     }
     /// # UpdateGrid
-    pub fn create_grid(&mut self) -> Grid {
-        let mut grid = array::from_fn(|_| array::from_fn(|_| Cell::default()));
-        // Update Grid cells with ships in each cell
-        for s in &mut self.ships {
-            if s.status != Status::Alive { continue }
-            // figure out which grid cell the ship is in
-            s.gx = ((s.x / GRID_SCALE as Float) as usize).clamp(0, GRID_WIDTH - 1);
-            s.gy = ((s.y / GRID_SCALE as Float) as usize).clamp(0, GRID_HEIGHT - 1);
-            grid[s.gx][s.gy].ships.push(*s);
-        }
-        grid
+    #[inline]
+    pub fn update_grid(&mut self) {
+        self.grid.update(&mut self.ships);
     }
-    pub fn move_ships(&mut self, grid: &Grid) {
+    /// Dead ships are only counted, `update_combat` removes them once they're gone from the screen
+    pub fn move_ships(&mut self) {
         let centroid = self.find_centroid();
-        self.ships.retain_mut(|s| {
+        for i in 0..self.ships.len() {
+            let mut s = self.ships[i];
             match s.status {
-                Status::Dead(ref mut f) => {
-                    // code handled by GUI
-                    *f += 1;
-                    *f < 10
-                }
-                Status::Alive => {
-                    s.move_ship(grid, &centroid);
-                    // code handled by GUI
-                    true
-                }
+                // code handled by GUI
+                Status::Dead(ref mut f) => *f = f.saturating_add(1),
+                // the ships before it have already moved, like in the original
+                Status::Alive => s.move_ship(&self.ships, &self.grid, &centroid),
             }
-        });
+            self.ships[i] = s;
+        }
     }
 }
 
@@ -347,7 +392,85 @@ pub struct Pos {
     y: Float,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct Cell {
-    pub ships: Vec<Ship>,
+/// # battleGRID
+/// The alive ships of every cell, as indices into `Combat::ships`.
+/// It's kept between frames so updating it doesn't allocate.
+/// Clones are empty, it gets rebuilt at the start of every frame anyway.
+#[derive(Debug)]
+pub struct Grid {
+    /// Cell `i` has `ships[starts[i]..starts[i + 1]]`
+    starts: [u32; GRID_CELLS + 1],
+    ships: Vec<u32>,
+    /// The ships of each team in every cell, so a ship doesn't have to look at each enemy around it
+    flocks: Vec<[Flock; 2]>,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self { starts: [0; GRID_CELLS + 1], ships: Vec::new(), flocks: Vec::new() }
+    }
+}
+
+impl Clone for Grid {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Sums over the ships of a team in a cell, as they were when the [`Grid`] got updated
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Flock {
+    pub count: ShipCount,
+    pub x: Float,
+    pub y: Float,
+    pub vx: Float,
+    pub vy: Float,
+}
+
+impl Grid {
+    #[inline]
+    fn index(gx: usize, gy: usize) -> usize {
+        gx * GRID_HEIGHT + gy
+    }
+    /// Indices of the alive ships in the cell, in order
+    #[inline]
+    pub fn cell(&self, gx: usize, gy: usize) -> &[u32] {
+        let i = Self::index(gx, gy);
+        &self.ships[self.starts[i] as usize..self.starts[i + 1] as usize]
+    }
+    #[inline]
+    pub fn flock(&self, gx: usize, gy: usize, team: Team) -> &Flock {
+        &self.flocks[Self::index(gx, gy)][team as usize]
+    }
+    /// Puts every alive ship in its cell, a counting sort by cell
+    pub fn update(&mut self, ships: &mut [Ship]) {
+        self.starts.fill(0);
+        self.flocks.clear();
+        self.flocks.resize(GRID_CELLS, Default::default());
+        for s in ships.iter_mut().filter(|s| s.status == Status::Alive) {
+            // figure out which grid cell the ship is in
+            s.gx = ((s.x / GRID_SCALE as Float) as usize).clamp(0, GRID_WIDTH - 1);
+            s.gy = ((s.y / GRID_SCALE as Float) as usize).clamp(0, GRID_HEIGHT - 1);
+            let i = Self::index(s.gx, s.gy);
+            self.starts[i + 1] += 1;
+            let flock = &mut self.flocks[i][s.team as usize];
+            flock.count += 1;
+            flock.x += s.x;
+            flock.y += s.y;
+            flock.vx += s.vx;
+            flock.vy += s.vy;
+        }
+        for i in 1..=GRID_CELLS {
+            self.starts[i] += self.starts[i - 1];
+        }
+
+        self.ships.clear();
+        self.ships.resize(self.starts[GRID_CELLS] as usize, 0);
+        let mut next = self.starts;
+        for (i, s) in ships.iter().enumerate().filter(|(_, s)| s.status == Status::Alive) {
+            let cell = &mut next[Self::index(s.gx, s.gy)];
+            self.ships[*cell as usize] = i as u32;
+            *cell += 1;
+        }
+    }
 }
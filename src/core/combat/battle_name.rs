@@ -1,4 +1,7 @@
+use std::fmt::{self, Display};
+
 use arrayvec::ArrayString;
+use serde::{Deserialize, Serialize};
 
 use crate::{Float, combat::{BattleID, Combat, MAX_BATTLENAME_LEN}, rng::PCRng};
 
@@ -6,14 +9,27 @@ pub const BATTLE_NAMES: &[&str] = &["Aboukir", "Abensberg", "Acre", "Alba de Tor
 
 pub type BattleNumbers = [BattleID; BATTLE_NAMES.len()];
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum BattleName {
     Id(BattleID),
     Name(ArrayString<MAX_BATTLENAME_LEN>),
 }
 
+/// `Battle <id>` until the battles get named
+impl Display for BattleName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BattleName::Id(id) => write!(f, "Battle {id}"),
+            BattleName::Name(name) => f.write_str(name),
+        }
+    }
+}
+
 impl Combat {
     pub fn update_battle_name(&mut self, rng: &mut PCRng) {
+        if self.battle_name_flag && matches!(self.battle_name, BattleName::Id(_)) {
+            self.battle_name = BattleName::Name(ArrayString::new());
+        }
         match &mut self.battle_name {
             BattleName::Id(id ) => *id += 1,
             BattleName::Name(name) => {
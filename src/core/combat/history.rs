@@ -0,0 +1,112 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{COMBAT_TICK, Float, Ticks, combat::{ShipCount, battle_name::BattleName}, util::ticks_10ms};
+
+/// Which side got wiped out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Winner {
+    Probes,
+    Drifters,
+    /// Both sides were still there when the clock ran out
+    Draw,
+}
+
+impl Display for Winner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Winner::Probes => "Probes",
+            Winner::Drifters => "Drifters",
+            Winner::Draw => "Draw",
+        })
+    }
+}
+
+/// One battle of the drift war, the last one of `Combat::history` is still going while `battles` is set
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BattleRecord {
+    pub name: BattleName,
+    /// The tick it started on
+    pub started: Ticks,
+    /// # (battleLEFTSHIPS, battleRIGHTSHIPS)
+    pub max_ships: (ShipCount, ShipCount),
+    /// How many probes or drifters a ship stands for
+    pub unit_size: Float,
+    /// Frames it lasted, see `master_battle_clock`
+    pub duration: u16,
+    /// `None` until it's over
+    pub winner: Option<Winner>,
+    pub honor_change: i64,
+    pub probes_lost: Float,
+    pub drifters_killed: Float,
+}
+
+impl BattleRecord {
+    pub fn new(name: BattleName, started: Ticks, max_ships: (ShipCount, ShipCount), unit_size: Float) -> Self {
+        Self {
+            name,
+            started,
+            max_ships,
+            unit_size,
+            duration: 0,
+            winner: None,
+            honor_change: 0,
+            probes_lost: 0.0,
+            drifters_killed: 0.0,
+        }
+    }
+    /// The tick it ended on, or the latest one while it's still going
+    #[inline]
+    pub fn ended(&self) -> Ticks {
+        self.started + ticks_10ms(COMBAT_TICK * self.duration as u32)
+    }
+}
+
+/// Totals over a whole history of battles
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WarSummary {
+    /// Including the one still going
+    pub battles: usize,
+    pub won: usize,
+    pub lost: usize,
+    pub drawn: usize,
+    pub honor_gained: i64,
+    pub honor_lost: i64,
+    pub probes_lost: Float,
+    pub drifters_killed: Float,
+    /// In frames, over the finished battles
+    pub mean_duration: Float,
+    /// Most ships a side had in a battle
+    pub largest_fleet: ShipCount,
+}
+
+impl WarSummary {
+    pub fn new(history: &[BattleRecord]) -> Self {
+        let mut summary = Self { battles: history.len(), ..Default::default() };
+        let mut duration = 0.0;
+        for record in history {
+            match record.winner {
+                Some(Winner::Probes) => summary.won += 1,
+                Some(Winner::Drifters) => summary.lost += 1,
+                Some(Winner::Draw) => summary.drawn += 1,
+                None => {},
+            }
+            if record.winner.is_some() {
+                duration += record.duration as Float;
+            }
+            match record.honor_change {
+                change if change > 0 => summary.honor_gained += change,
+                change => summary.honor_lost -= change,
+            }
+            summary.probes_lost += record.probes_lost;
+            summary.drifters_killed += record.drifters_killed;
+            summary.largest_fleet = summary.largest_fleet.max(record.max_ships.0).max(record.max_ships.1);
+        }
+        let finished = summary.won + summary.lost + summary.drawn;
+        if finished > 0 {
+            summary.mean_duration = duration / finished as Float;
+        }
+        summary
+    }
+}
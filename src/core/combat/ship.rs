@@ -1,10 +1,12 @@
-use crate::{Float, combat::{BATTLE_HEIGHT, BATTLE_WIDTH, GRID_WIDTH, Grid, Pos}, rng::PCRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{Float, combat::{BATTLE_HEIGHT, BATTLE_WIDTH, GRID_HEIGHT, GRID_WIDTH, Grid, Pos}, rng::PCRng};
 
 /// # battleMAXSPEED
 pub const MAX_SPEED: Float = 2.0;
 pub const CENTROID_ACCEL: Float = 0.001;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Ship {
     /// # alive / framesDead
     pub status: Status,
@@ -20,8 +22,9 @@ pub struct Ship {
 impl Ship {
     pub fn new_rng(team: Team, rng: &mut PCRng) -> Self {
         let x_offset = match team { Team::Left => 0.0, Team::Right => 0.8 };
-        let x = (rng.random_float_no_best() * 0.2 + x_offset) * GRID_WIDTH as Float;
-        let y = rng.random_float_no_best();
+        // on a fifth of the field on their side, over its whole height
+        let x = (rng.random_float_no_best() * 0.2 + x_offset) * BATTLE_WIDTH as Float;
+        let y = rng.random_float_no_best() * BATTLE_HEIGHT as Float;
         let vx_dir = match team { Team::Left => 1.0, Team::Right => -1.0 };
         let vx = vx_dir * rng.random_float_no_best() * MAX_SPEED;
         let vy = rng.random_float_no_best() - 0.5;
@@ -37,37 +40,35 @@ impl Ship {
             vy,
         }
     }
-    pub fn move_ship(&mut self, grid: &Grid, centroid: &Pos) {
+    /// `ships` are the ones `grid` indexes, this ship among them
+    pub fn move_ship(&mut self, ships: &[Ship], grid: &Grid, centroid: &Pos) {
         self.vx += (centroid.x - self.x) * CENTROID_ACCEL;
         self.vy += (centroid.y - self.y) * CENTROID_ACCEL;
         
-        for y in 0.max(self.gy - 1)..BATTLE_HEIGHT.min(self.gy + 2) {
-            for x in 0.max(self.gx - 1)..BATTLE_WIDTH.min(self.gx + 2) {
-                let cell = &grid[x][y];
-                if cell.ships.len() < 2 { continue }
-                let mut teammates_considered: u8 = 0;
-                for othership in &cell.ships {
-                    if matches!(othership.status, Status::Dead(_)) { continue }
-                    
-                    if othership.team == self.team {
-                        teammates_considered += 1;
-                        if teammates_considered > 3 { continue }
-
-                        // mild acceleration to match teammates
-                        self.vx += othership.vx * 0.01;
-                        self.vy += othership.vy * 0.01;
+        for gx in self.gx.saturating_sub(1)..GRID_WIDTH.min(self.gx + 2) {
+            for gy in self.gy.saturating_sub(1)..GRID_HEIGHT.min(self.gy + 2) {
+                let cell = grid.cell(gx, gy);
+                if cell.len() < 2 { continue }
+                // only the first few teammates count
+                let teammates = cell.iter().map(|&i| &ships[i as usize]).filter(|s| s.status == Status::Alive && s.team == self.team);
+                for othership in teammates.take(3) {
+                    // mild acceleration to match teammates
+                    self.vx += othership.vx * 0.01;
+                    self.vy += othership.vy * 0.01;
 
-                        // mild acceleration to get space from teammates
-                        self.vx -= (othership.x - self.x) * 0.1;
-                        self.vy -= (othership.y - self.y) * 0.1;
-                    } else {
-                        self.vx += othership.vx * 0.2;
-                        self.vy += othership.vy * 0.2;
-                        // acceleration toward enemies
-                        self.vx += (othership.x - self.x) * 0.2;
-                        self.vy += (othership.y - self.y) * 0.2;
-                    }
+                    // mild acceleration to get space from teammates
+                    self.vx -= (othership.x - self.x) * 0.1;
+                    self.vy -= (othership.y - self.y) * 0.1;
                 }
+
+                // every enemy counts, all at once
+                let enemies = grid.flock(gx, gy, self.team.enemy());
+                let count = enemies.count as Float;
+                self.vx += enemies.vx * 0.2;
+                self.vy += enemies.vy * 0.2;
+                // acceleration toward enemies
+                self.vx += (enemies.x - self.x * count) * 0.2;
+                self.vy += (enemies.y - self.y * count) * 0.2;
             }
         }
 
@@ -97,13 +98,23 @@ impl Ship {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Team {
     Left = 0,
     Right = 1,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl Team {
+    #[inline]
+    pub fn enemy(self) -> Self {
+        match self {
+            Team::Left => Team::Right,
+            Team::Right => Team::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Status {
     Alive,
     Dead(u8),
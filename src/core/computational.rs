@@ -2,12 +2,13 @@ use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{core::Float, PaperClips};
+use crate::{core::Float, PaperClips, Ticks, event::GameEvent};
 
 pub const CREATIVITY_THRESHOLD: Float = 400.0;
 pub const MEM_SIZE: u32 = 1000;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Computational {
     pub comp_flag: bool,
 
@@ -119,6 +120,56 @@ impl Computational {
             self.creativity_counter = 0;
         }
     }
+    /// Same as `ticks` main ticks of [`Computational::calculate_operations`],
+    /// and of [`Computational::calculate_creativity`] while the operations are full.
+    ///
+    /// Only the fading of the temporary operations from the quantum chips is done tick by tick,
+    /// the rest is closed-form.
+    pub fn advance(&mut self, mut ticks: Ticks) {
+        while self.temp_ops > 0.0 && ticks > 0 {
+            self.calculate_operations();
+            if self.creativity_flag && self.operations >= self.max_operations() as Float {
+                self.calculate_creativity();
+            }
+            ticks -= 1;
+        }
+        if ticks == 0 {
+            return
+        }
+
+        let max = self.max_operations() as Float;
+        let per_tick = self.processors as Float / 10.0;
+        // ticks until `standard_ops` is full, creativity starts on the next one
+        let filling = if self.standard_ops >= max {
+            0
+        } else if per_tick > 0.0 {
+            ((max - self.standard_ops) / per_tick).ceil() as Ticks
+        } else {
+            Ticks::MAX
+        };
+        // `operations` lags one tick behind `standard_ops`
+        self.operations = (self.standard_ops + per_tick * (ticks - 1) as Float).min(max).floor();
+        self.standard_ops = (self.standard_ops + per_tick * ticks as Float).min(max);
+
+        if self.creativity_flag {
+            self.advance_creativity(ticks.saturating_sub(filling));
+        }
+    }
+    /// Same as calling [`Computational::calculate_creativity`] `calls` times
+    pub fn advance_creativity(&mut self, calls: Ticks) {
+        let ss = self.creativity_speed * (self.prestige_s / 10.0 + 1.0);
+        let creativity_check = CREATIVITY_THRESHOLD / ss;
+        // calls for the counter to reach the check
+        let period = (creativity_check.ceil() as Ticks).max(1);
+        let total = (self.creativity_counter as Ticks).min(period - 1) + calls;
+
+        let gain = match creativity_check >= 1.0 {
+            true => 1.0,
+            false => ss / CREATIVITY_THRESHOLD,
+        };
+        self.creativity += (total / period) as Float * gain;
+        self.creativity_counter = (total % period) as u32;
+    }
     /// # `memory * 1000`
     #[inline]
     pub fn max_operations(&self) -> u32 {
@@ -129,37 +180,49 @@ impl Computational {
 impl PaperClips {
     pub fn calculate_trust(&mut self) {
         if self.business.clips >= self.computational.next_trust {
-            self.computational.trust += 1;
-            self.console.push("Production target met: TRUST INCREASED, additional processor/memory capacity granted");
+            self.gain_trust(1);
+            self.log("Production target met: TRUST INCREASED, additional processor/memory capacity granted");
             let fib_next = self.computational.fib.iter().sum::<Float>();
             self.computational.next_trust = fib_next * 1000.0;
             self.computational.fib = [self.computational.fib[1], fib_next];
         }
     }
+    pub fn gain_trust(&mut self, amount: i32) {
+        self.computational.trust += amount;
+        self.emit(GameEvent::TrustGained { amount, trust: self.computational.trust });
+    }
     /// # addProc()
     pub fn add_processors(&mut self) {
-        if self.computational.trust > 0 /* || swarmGifts > 0 */ {
+        if self.can_add_compute() {
             let processors = &mut self.computational.processors;
             *processors += 1;
             let proc_float = *processors as Float;
             self.computational.creativity_speed = proc_float.log10() * proc_float.powf(1.1) + proc_float - 1.0;
-            if self.human_flag {
-                // swarmGifts -= 1;
+            if !self.human_flag() {
+                self.factory.swarm_gifts -= 1.0;
             }
-            self.console.push(match self.computational.creativity_flag {
+            self.log(match self.computational.creativity_flag {
                 false => "Processor added, operations per sec increased",
                 true => "Processor added, operations (or creativity) per sec increased",
             });
         } 
     }
+    /// # trust > 0 || swarmGifts > 0
+    /// Humans pay with trust, after them each processor or memory is a whole swarm gift
+    fn can_add_compute(&self) -> bool {
+        match self.human_flag() {
+            true => self.computational.trust > 0,
+            false => self.factory.swarm_gifts >= 1.0,
+        }
+    }
     /// # addMem()
     pub fn add_memory(&mut self) {
-        if self.computational.trust > 0 /* || swarmGifts > 0 */ {
+        if self.can_add_compute() {
             self.computational.memory += 1;
-            if self.human_flag {
-                // swarmGifts -= 1;
+            if !self.human_flag() {
+                self.factory.swarm_gifts -= 1.0;
             }
-            self.console.push("Memory added, max operations increased");
+            self.log("Memory added, max operations increased");
         }
     }
 }
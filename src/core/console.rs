@@ -1,13 +1,64 @@
-use std::{borrow::Cow, collections::VecDeque};
+use std::{borrow::Cow, collections::VecDeque, fmt::Write, fs, io, path::Path, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
+use crate::{PaperClips, Ticks, util::ticks_to_duration};
+
 const DEFAULT_MESSAGES_SIZE: usize = 5;
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+/// A message of the console, as kept in the history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsoleEntry {
+    /// The tick the message got pushed on
+    pub ticks: Ticks,
+    pub message: Cow<'static, str>,
+}
+
+impl ConsoleEntry {
+    /// The game time when the message got pushed
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        ticks_to_duration(self.ticks)
+    }
+}
+
+/// How the history gets exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One `[h:mm:ss.cc] message` line per entry
+    Text,
+    /// An array of [`ConsoleEntry`]
+    Json,
+}
+
+impl LogFormat {
+    #[inline]
+    pub fn extension(self) -> &'static str {
+        match self {
+            LogFormat::Text => "txt",
+            LogFormat::Json => "json",
+        }
+    }
+    /// `.json` files get [`LogFormat::Json`], anything else [`LogFormat::Text`]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            true => LogFormat::Json,
+            false => LogFormat::Text,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Console {
     pub max_messages: usize,
     pub messages: VecDeque<Cow<'static, str>>,
+    /// Every message, oldest first, up to `max_history` of them
+    pub history: VecDeque<ConsoleEntry>,
+    /// Not saved, so older saves get the current default
+    #[serde(skip)]
+    pub max_history: usize,
 }
 
 impl Default for Console {
@@ -15,15 +66,64 @@ impl Default for Console {
         Self {
             max_messages: DEFAULT_MESSAGES_SIZE,
             messages: VecDeque::with_capacity(DEFAULT_MESSAGES_SIZE),
+            history: VecDeque::new(),
+            max_history: DEFAULT_HISTORY_SIZE,
         }
     }
 }
 
 impl Console {
-    pub fn push(&mut self, msg: impl Into<Cow<'static, str>>) {
-        self.messages.push_back(msg.into());
+    /// `ticks` is what the entry of the history gets stamped with, see [`PaperClips::log`]
+    pub fn push(&mut self, ticks: Ticks, msg: impl Into<Cow<'static, str>>) {
+        let msg = msg.into();
+
+        if self.max_history > 0 {
+            while self.history.len() >= self.max_history {
+                self.history.pop_front();
+            }
+            self.history.push_back(ConsoleEntry { ticks, message: msg.clone() });
+        }
+
+        self.messages.push_back(msg);
         if self.messages.len() > self.max_messages {
             self.messages.pop_front();
         }
     }
+    /// The entries of the history containing `query`, ignoring case
+    pub fn search<'a>(&'a self, query: &str) -> impl DoubleEndedIterator<Item = &'a ConsoleEntry> {
+        let query = query.to_lowercase();
+        self.history.iter().filter(move |entry| query.is_empty() || entry.message.to_lowercase().contains(&query))
+    }
+
+    pub fn history_to_string(&self, format: LogFormat) -> io::Result<String> {
+        match format {
+            LogFormat::Text => {
+                let mut s = String::new();
+                for entry in &self.history {
+                    let _ = writeln!(s, "[{}] {}", format_game_time(entry.elapsed()), entry.message);
+                }
+                Ok(s)
+            }
+            LogFormat::Json => Ok(serde_json::to_string_pretty(&self.history)?),
+        }
+    }
+    /// The format is picked from the extension, see [`LogFormat::from_path`]
+    pub fn export_history(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.history_to_string(LogFormat::from_path(path))?)
+    }
+}
+
+impl PaperClips {
+    /// Pushes `msg` to the console, stamped with the current tick
+    #[inline]
+    pub fn log(&mut self, msg: impl Into<Cow<'static, str>>) {
+        self.console.push(self.ticks, msg);
+    }
+}
+
+/// `h:mm:ss.cc`, the format of the exported history
+pub fn format_game_time(t: Duration) -> String {
+    let secs = t.as_secs();
+    format!("{}:{:02}:{:02}.{:02}", secs / 3600, secs % 3600 / 60, secs % 60, t.subsec_millis() / 10)
 }
@@ -1,8 +1,23 @@
-use crate::{PaperClips, project::{PROJECT_148, PROJECT_211, PROJECT_212, PROJECT_213, PROJECT_215, PROJECT_216}};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Default)]
+use crate::{Float, PaperClips, progression::Progression, project::{PROJECT_148, PROJECT_211, PROJECT_212, PROJECT_213, PROJECT_215, PROJECT_216}, util::number_cruncher};
+
+/// Lines of the credits, and the value of `timer6` they show up at
+pub const CREDITS: [(u16, &str); 5] = [
+    (500, "Universal Paperclips"),
+    (600, "a game by Frank Lantz"),
+    (700, "combat programming by Bennett Foddy"),
+    (800, "'Riversong' by Tonto's Expanding Headband used by kind permission of Malcolm Cecil"),
+    (900, "© 2017 Everybody House Games"),
+];
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct End {
     pub dismantle: Dismantle,
+    /// # finalClips
+    /// Clips made by hand out of the recovered wire
+    pub final_clips: Float,
     pub timer1: u8,
     pub timer2: u8,
     pub timer3: u8,
@@ -11,7 +26,7 @@ pub struct End {
     pub timer6: u16,
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Dismantle {
     #[default]
     None = 0,
@@ -132,7 +147,8 @@ impl PaperClips {
         if self.projects.is_active(PROJECT_215) {
             self.end.timer5 = self.end.timer5.saturating_add(1);
         }
-        if self.projects.is_active(PROJECT_216) && self.wire.count <= 0.0 {
+        // the last clip can't be made out of less than an inch of wire
+        if self.projects.is_active(PROJECT_216) && self.wire.count < 1.0 {
             self.end.timer6 = self.end.timer6.saturating_add(1);
         }
 
@@ -141,26 +157,22 @@ impl PaperClips {
             // creationDivElement.style.display = "none";
         }
 
-        if self.end.timer6 >= 500 && self.milestone_flag == 15 {
+        if self.end.timer6 >= CREDITS[0].0 && self.advance(Progression::Credits) {
             self.threnody.play();
-            self.console.push("Universal Paperclips");
-            self.milestone_flag += 1;
-        }
-        if self.end.timer6 >= 600 && self.milestone_flag == 16 {
-            self.console.push("a game by Frank Lantz");
-            self.milestone_flag += 1;
-        }
-        if self.end.timer6 >= 700 && self.milestone_flag == 17 {
-            self.console.push("combat programming by Bennett Foddy");
-            self.milestone_flag += 1;
         }
-        if self.end.timer6 >= 800 && self.milestone_flag == 18 {
-            self.console.push("'Riversong' by Tonto's Expanding Headband used by kind permission of Malcolm Cecil");
-            self.milestone_flag += 1;
+        for (time, line) in CREDITS {
+            if self.end.timer6 == time {
+                self.log(line);
+            }
         }
-        if self.end.timer6 >= 900 && self.milestone_flag == 19 {
-            self.console.push("© 2017 Everybody House Games");
-            self.milestone_flag += 1;
+    }
+
+    /// Turns dismantled things back into clips
+    pub fn recover_clips(&mut self, amount: Float) {
+        self.business.clips += amount;
+        self.business.unused_clips += amount;
+        if amount >= 1.0 {
+            self.log(format!("{} clips recovered", number_cruncher(amount, None)));
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use std::{collections::VecDeque, fmt::{self, Display}};
+
+use serde::Serialize;
+
+use crate::{
+    Float, PaperClips, Ticks,
+    combat::Honor,
+    investments::Symbol,
+    progression::Progression,
+    project::Project,
+    strategy::strategies::Strat,
+};
+
+/// How many events are kept when nobody takes them, the oldest ones get dropped first
+pub const MAX_EVENTS: usize = 1000;
+
+/// Something noteworthy that happened in the game, emitted next to the console text
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum GameEvent {
+    /// The game moved to a new phase, see [`PaperClips::advance`]
+    Progressed { from: Progression, to: Progression },
+    /// The milestone at this index in `milestone_check` got reached
+    MilestoneReached(u8),
+    ProjectBought(&'static Project),
+    /// `trust` is the trust after the gain
+    TrustGained { amount: i32, trust: i32 },
+    /// `place` is 1 for the winner, `yomi` includes the placement reward
+    TourneyResult { strat: &'static Strat, score: u16, place: usize, yomi: Float },
+    BattleWon { honor: Honor },
+    BattleLost { honor: Honor },
+    /// `total` is what went into the bankroll
+    StockSold { symbol: Symbol, total: Float },
+    /// How much computational capacity the swarm gave
+    SwarmGift(Float),
+    /// One of the Drift King's messages got read
+    DriftMessage(&'static Project),
+}
+
+impl Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Progressed { from, to } => write!(f, "Progressed from {from} to {to}"),
+            Self::MilestoneReached(milestone) => write!(f, "Milestone {milestone} reached"),
+            Self::ProjectBought(project) => write!(f, "Bought {}", project.name),
+            Self::TrustGained { amount, trust } => write!(f, "Trust +{amount} ({trust})"),
+            Self::TourneyResult { strat, score, place, yomi } => write!(f, "{} scored {score}, place {place}, yomi +{yomi:.0}", strat.name),
+            Self::BattleWon { honor } => write!(f, "Battle won, honor +{honor}"),
+            Self::BattleLost { honor } => write!(f, "Battle lost, honor -{honor}"),
+            Self::StockSold { symbol, total } => write!(f, "Sold {symbol} for ${total:.2}"),
+            Self::SwarmGift(gift) => write!(f, "Swarm gift of {gift:.0}"),
+            Self::DriftMessage(project) => write!(f, "Drift King message {}", project.name),
+        }
+    }
+}
+
+impl PaperClips {
+    /// Queues `event`, stamped with the current tick
+    pub fn emit(&mut self, event: GameEvent) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back((self.ticks, event));
+    }
+    /// Takes the events that happened since the last call
+    pub fn take_events(&mut self) -> VecDeque<(Ticks, GameEvent)> {
+        std::mem::take(&mut self.events)
+    }
+}
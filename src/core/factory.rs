@@ -2,16 +2,21 @@ use std::{f64::consts::E, mem::take};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Float, PaperClips, project::PROJECT_130};
+use crate::{Float, PaperClips, event::GameEvent, progression::Progression, project::{PROJECT_127, PROJECT_130}};
 
 pub const FACTORY_COST: Float = 100000000.0;
 pub const HARVESTER_COST: Float = 1000000.0;
 pub const WIRE_DRONE_COST: Float = 1000000.0;
 pub const BATTERY_SIZE: u32 = 10000;
+/// How many drones can be bought at once, as on the buttons
+pub const DRONE_AMOUNTS: [u32; 4] = [1, 10, 100, 1000];
+/// How many solar farms or batteries can be bought at once
+pub const POWER_AMOUNTS: [u32; 3] = [1, 10, 100];
 pub const SYNCH_COST: Float = 5000.0;
 pub const GIFT_PERIOD: Float = 125000.0;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Factory {
     /// # storedPower
     pub stored_power: Float,
@@ -128,7 +133,7 @@ pub struct Factory {
 
 impl Default for Factory {
     fn default() -> Self {
-        Self {
+        let mut factory = Self {
             stored_power: 0.0,
 
             harvester_flag: false,
@@ -197,11 +202,80 @@ impl Default for Factory {
 
             acquired_matter_per_tick: 0.0,
             created_wire_per_tick: 0.0,
-        }
+        };
+        factory.update_harvester_drone_prices();
+        factory.update_wire_drone_prices();
+        factory.update_farm_prices();
+        factory.update_battery_prices();
+        factory
     }
 }
 
 impl PaperClips {
+    /// Solar farms and batteries can be built, until space exploration
+    #[inline]
+    pub fn power_grid_flag(&self) -> bool {
+        self.projects.is_active(PROJECT_127) && !self.space_flag()
+    }
+
+    /// # makeFactory
+    pub fn make_factory(&mut self) {
+        self.business.unused_clips -= self.factory.factory_cost;
+        self.factory.factory_bill += self.factory.factory_cost;
+        self.factory.factory_level += 1.0;
+
+        let fcmod = match self.factory.factory_level as u32 {
+            1..=7 => 11.0 - self.factory.factory_level,
+            8..=12 => 2.0,
+            13..=19 => 1.5,
+            20..=38 => 1.25,
+            39..=78 => 1.15,
+            79.. => 1.10,
+            0 => 1.0,
+        };
+        self.factory.factory_cost *= fcmod;
+    }
+    /// # makeHarvester
+    pub fn make_harvester(&mut self, amount: u32) {
+        for _ in 0..amount {
+            self.business.unused_clips -= self.factory.harvester_cost;
+            self.factory.harvester_bill += self.factory.harvester_cost;
+            self.factory.harvester_level += 1.0;
+            self.factory.harvester_cost = drone_price(self.factory.harvester_level, 1);
+        }
+        self.factory.update_harvester_drone_prices();
+    }
+    /// # makeWireDrone
+    pub fn make_wire_drone(&mut self, amount: u32) {
+        for _ in 0..amount {
+            self.business.unused_clips -= self.factory.wire_drone_cost;
+            self.factory.wire_drone_bill += self.factory.wire_drone_cost;
+            self.factory.wire_drone_level += 1.0;
+            self.factory.wire_drone_cost = drone_price(self.factory.wire_drone_level, 1);
+        }
+        self.factory.update_wire_drone_prices();
+    }
+    /// # makeFarm
+    pub fn make_farm(&mut self, amount: u32) {
+        for _ in 0..amount {
+            self.business.unused_clips -= self.factory.farm_cost;
+            self.factory.farm_bill += self.factory.farm_cost;
+            self.factory.farm_level += 1;
+            self.factory.farm_cost = farm_price(self.factory.farm_level as Float, 1);
+        }
+        self.factory.update_farm_prices();
+    }
+    /// # makeBattery
+    pub fn make_battery(&mut self, amount: u32) {
+        for _ in 0..amount {
+            self.business.unused_clips -= self.factory.battery_cost;
+            self.factory.battery_bill += self.factory.battery_cost;
+            self.factory.battery_level += 1;
+            self.factory.battery_cost = battery_price(self.factory.battery_level as Float, 1);
+        }
+        self.factory.update_battery_prices();
+    }
+
     pub fn factory_reboot(&mut self) {
         self.factory.factory_level = 0.0;
         self.business.unused_clips += take(&mut self.factory.factory_bill);
@@ -233,7 +307,7 @@ impl PaperClips {
     }
 
     pub fn update_power(&mut self) {
-        if !self.human_flag && !self.space.space_flag {
+        if !self.human_flag() && !self.space_flag() {
             let supply = self.factory.power_supply();
             let d_demand = self.factory.power_drone_demand();
             let f_demand = self.factory.power_factory_demand();
@@ -280,7 +354,7 @@ impl PaperClips {
             self.factory.boredom_flag = true;
             self.factory.boredom_level = 0;
             if !self.factory.boredom_msg {
-                self.console.push("No matter to harvest. Inactivity has caused the Swarm to become bored");
+                self.log("No matter to harvest. Inactivity has caused the Swarm to become bored");
                 self.factory.boredom_msg = true;
             }
         }
@@ -298,7 +372,7 @@ impl PaperClips {
         if self.factory.disorg_counter >= 100.0 {
             self.factory.disorg_flag = true;
             if !self.factory.disorg_msg {
-                self.console.push("Imbalance between Harvester and Wire Drone levels has disorganized the Swarm");
+                self.log("Imbalance between Harvester and Wire Drone levels has disorganized the Swarm");
                 self.factory.disorg_msg = true;
             }
         }
@@ -308,8 +382,9 @@ impl PaperClips {
         if self.factory.gift_countdown <= 0.0 {
             let next_gift = (d.log10().round() * self.factory.swarm_slider / 100.0).max(1.0);
             self.factory.swarm_gifts += next_gift;
-            if self.milestone_flag < 15 {
-                self.console.push(format!("The swarm has generated a gift of {next_gift:.0} additional computational capacity"));
+            self.emit(GameEvent::SwarmGift(next_gift));
+            if self.progression < Progression::Endgame {
+                self.log(format!("The swarm has generated a gift of {next_gift:.0} additional computational capacity"));
             }
             self.factory.gift_bits = 0.0;
         }
@@ -326,7 +401,7 @@ impl PaperClips {
                 SwarmStatus::None
             } else if d == 1.0 {
                 SwarmStatus::Lonely
-            } else if self.space.space_flag && self.projects.is_active(PROJECT_130) {
+            } else if self.space_flag() && self.projects.is_active(PROJECT_130) {
                 SwarmStatus::NoResponse
             } else if self.factory.pow_mod == 0.0 {
                 SwarmStatus::Sleeping
@@ -400,10 +475,23 @@ pub enum SwarmStatus {
     NoResponse = 9,
 }
 
+/// Price of the next `amount` harvester or wire drones when `level` are already built
+pub fn drone_price(level: Float, amount: u32) -> Float {
+    (1..=amount).map(|i| (level + i as Float).powf(2.25)).sum::<Float>() * 1000000.0
+}
+/// Price of the next `amount` solar farms when `level` are already built
+pub fn farm_price(level: Float, amount: u32) -> Float {
+    (1..=amount).map(|i| (level + i as Float).powf(2.78)).sum::<Float>() * 100000000.0
+}
+/// Price of the next `amount` batteries when `level` are already built
+pub fn battery_price(level: Float, amount: u32) -> Float {
+    (1..=amount).map(|i| (level + i as Float).powf(2.54)).sum::<Float>() * 1000000.0
+}
+
 macro_rules! update_prices {
-    ($($store:expr => $amt:literal $lvl:expr)*) => {
+    ($price:ident: $($store:expr => $amt:literal $lvl:expr)*) => {
         $({
-            $store = (1..=$amt).map(|i| ($lvl as Float + i as Float).powf(2.25)).sum::<Float>() * 1000000.0
+            $store = $price($lvl as Float, $amt)
         })*
     };
 }
@@ -420,34 +508,72 @@ impl Factory {
     }
     #[inline]
     pub const fn power_factory_demand(&self) -> Float {
-        self.factory_level as Float * self.factory_power_rate
+        self.factory_level as Float * self.factory_power_rate / 100.0
     }
     #[inline]
     pub const fn battery_cap(&self) -> u32 {
         self.battery_level * BATTERY_SIZE
     }
+    /// Price of the next `amount` harvester drones, `p10h` and friends are kept up to date for the buttons
+    pub fn harvester_price(&self, amount: u32) -> Float {
+        match amount {
+            1 => self.harvester_cost,
+            10 => self.p10h,
+            100 => self.p100h,
+            1000 => self.p1000h,
+            _ => drone_price(self.harvester_level, amount),
+        }
+    }
+    /// Price of the next `amount` wire drones
+    pub fn wire_drone_price(&self, amount: u32) -> Float {
+        match amount {
+            1 => self.wire_drone_cost,
+            10 => self.p10w,
+            100 => self.p100w,
+            1000 => self.p1000w,
+            _ => drone_price(self.wire_drone_level, amount),
+        }
+    }
     pub fn update_harvester_drone_prices(&mut self) {
-        update_prices!{
+        update_prices!{drone_price:
             self.p10h => 10 self.harvester_level
             self.p100h => 100 self.harvester_level
             self.p1000h => 1000 self.harvester_level
         }
     }
     pub fn update_wire_drone_prices(&mut self) {
-        update_prices!{
+        update_prices!{drone_price:
             self.p10w => 10 self.wire_drone_level
             self.p100w => 100 self.wire_drone_level
             self.p1000w => 1000 self.wire_drone_level
         }
     }
+    /// Price of the next `amount` solar farms
+    pub fn farm_price(&self, amount: u32) -> Float {
+        match amount {
+            1 => self.farm_cost,
+            10 => self.p10f,
+            100 => self.p100f,
+            _ => farm_price(self.farm_level as Float, amount),
+        }
+    }
+    /// Price of the next `amount` batteries
+    pub fn battery_price(&self, amount: u32) -> Float {
+        match amount {
+            1 => self.battery_cost,
+            10 => self.p10b,
+            100 => self.p100b,
+            _ => battery_price(self.battery_level as Float, amount),
+        }
+    }
     pub fn update_farm_prices(&mut self) {
-        update_prices!{
+        update_prices!{farm_price:
             self.p10f => 10 self.farm_level
             self.p100f => 100 self.farm_level
         }
     }
     pub fn update_battery_prices(&mut self) {
-        update_prices!{
+        update_prices!{battery_price:
             self.p10b => 10 self.battery_level
             self.p100b => 100 self.battery_level
         }
@@ -0,0 +1,486 @@
+use std::fmt::{self, Display};
+
+use arrayvec::ArrayString;
+use serde_json::{Map, Value};
+
+use crate::{
+    Float, PaperClips,
+    combat::battle_name::BattleName,
+    end::Dismantle,
+    factory::SwarmStatus,
+    investments::{Riskiness, Stock},
+    progression::Progression,
+    project::{PROJECTS, Project, ProjectStatus},
+    strategy::{Move, strategies::ALL_STRATS},
+};
+
+/// localStorage key holding the main state
+pub const SAVE_GAME_KEY: &str = "saveGame";
+pub const PROJECTS_USES_KEY: &str = "saveProjectsUses";
+pub const PROJECTS_FLAGS_KEY: &str = "saveProjectsFlags";
+pub const PROJECTS_ACTIVE_KEY: &str = "saveProjectsActive";
+pub const STRATS_ACTIVE_KEY: &str = "saveStratsActive";
+pub const PRESTIGE_KEY: &str = "savePrestige";
+
+#[derive(Debug)]
+pub enum ImportError {
+    Json(serde_json::Error),
+    /// A required localStorage key isn't in the blob
+    MissingKey(&'static str),
+    /// The key exists, but doesn't have the shape the original game writes
+    InvalidValue(&'static str),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Json(e) => write!(f, "invalid json: {e}"),
+            ImportError::MissingKey(key) => write!(f, "missing `{key}`"),
+            ImportError::InvalidValue(key) => write!(f, "`{key}` doesn't look like an Universal Paperclips save"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<serde_json::Error> for ImportError {
+    fn from(value: serde_json::Error) -> Self { ImportError::Json(value) }
+}
+
+/// A value of the original save, JavaScript is very loose with types
+/// (flags are `0`/`1` or `true`/`false`, the strat picker stores a string...)
+trait FromJs: Sized {
+    fn from_js(value: &Value) -> Option<Self>;
+}
+
+fn js_number(value: &Value) -> Option<Float> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(*b as u8 as Float),
+        Value::String(s) => s.trim().parse().ok(),
+        // `JSON.stringify` turns Infinity and NaN into null, those keep their default
+        _ => None,
+    }
+}
+
+macro_rules! from_js_number {
+    ($($t:ty)*) => {
+        $(
+            impl FromJs for $t {
+                fn from_js(value: &Value) -> Option<Self> {
+                    js_number(value).map(|n| n as $t)
+                }
+            }
+        )*
+    };
+}
+
+from_js_number!(Float u8 u16 u32 u64 u128 i32 usize);
+
+impl FromJs for bool {
+    fn from_js(value: &Value) -> Option<Self> {
+        js_number(value).map(|n| n != 0.0 && !n.is_nan())
+    }
+}
+
+/// Parses a localStorage value, which is a JSON string containing JSON
+fn parse_item(value: &Value) -> Result<Value, serde_json::Error> {
+    match value {
+        Value::String(s) => serde_json::from_str(s),
+        value => Ok(value.clone()),
+    }
+}
+
+struct JsSave {
+    game: Map<String, Value>,
+    projects_uses: Vec<Value>,
+    projects_flags: Vec<Value>,
+    projects_active: Vec<Value>,
+    strats_active: Vec<Value>,
+    prestige: Map<String, Value>,
+}
+
+impl JsSave {
+    fn parse(s: &str) -> Result<Self, ImportError> {
+        let Value::Object(storage) = serde_json::from_str(s)? else {
+            return Err(ImportError::InvalidValue(SAVE_GAME_KEY));
+        };
+
+        let item = |key: &'static str| -> Result<Option<Value>, ImportError> {
+            storage.get(key).map(parse_item).transpose().map_err(ImportError::from)
+        };
+        let array = |key: &'static str| -> Result<Vec<Value>, ImportError> {
+            match item(key)? {
+                None | Some(Value::Null) => Ok(Vec::new()),
+                Some(Value::Array(array)) => Ok(array),
+                Some(_) => Err(ImportError::InvalidValue(key)),
+            }
+        };
+
+        let game = match item(SAVE_GAME_KEY)? {
+            Some(Value::Object(game)) => game,
+            Some(_) => return Err(ImportError::InvalidValue(SAVE_GAME_KEY)),
+            None => return Err(ImportError::MissingKey(SAVE_GAME_KEY)),
+        };
+        let prestige = match item(PRESTIGE_KEY)? {
+            Some(Value::Object(prestige)) => prestige,
+            _ => Map::new(),
+        };
+
+        Ok(Self {
+            game,
+            projects_uses: array(PROJECTS_USES_KEY)?,
+            projects_flags: array(PROJECTS_FLAGS_KEY)?,
+            projects_active: array(PROJECTS_ACTIVE_KEY)?,
+            strats_active: array(STRATS_ACTIVE_KEY)?,
+            prestige,
+        })
+    }
+
+    fn get<T: FromJs>(&self, key: &str) -> Option<T> {
+        self.game.get(key).and_then(T::from_js)
+    }
+    fn prestige(&self, key: &str) -> Option<Float> {
+        self.prestige.get(key).and_then(Float::from_js)
+    }
+}
+
+/// Id of the project's button in the original, which is what `saveProjectsActive` stores
+fn js_project_id(project: &Project) -> String {
+    let number = project.name.trim_start_matches("PROJECT_").to_lowercase();
+    format!("projectButton{number}")
+}
+
+impl PaperClips {
+    /// Builds the state from the localStorage of the original game.
+    ///
+    /// `s` is a JSON object with the localStorage keys (`saveGame`, `saveProjectsUses`, ...),
+    /// which is what `JSON.stringify(localStorage)` prints in the browser console.
+    /// Values can either be the strings stored by the game, or already parsed JSON.
+    ///
+    /// Only `saveGame` is required, any value missing from it keeps its default.
+    pub fn from_js_save(s: &str) -> Result<Self, ImportError> {
+        let save = JsSave::parse(s)?;
+        let mut pc = PaperClips::default();
+
+        macro_rules! import {
+            ($($key:literal => $($field:ident).+),* $(,)?) => {
+                $(
+                    if let Some(value) = save.get($key) {
+                        pc.$($field).+ = value;
+                    }
+                )*
+            };
+        }
+
+        import! {
+            "ticks" => ticks,
+            "milestoneFlag" => milestones,
+
+            "endTimer1" => end.timer1,
+            "endTimer2" => end.timer2,
+            "endTimer3" => end.timer3,
+            "endTimer4" => end.timer4,
+            "endTimer5" => end.timer5,
+            "endTimer6" => end.timer6,
+            "finalClips" => end.final_clips,
+
+            "funds" => business.funds,
+            "clips" => business.clips,
+            "unsoldClips" => business.unsold_clips,
+            "margin" => business.margin,
+            "demand" => business.demand,
+            "adCost" => business.ad_cost,
+            "marketingLvl" => business.marketing_lvl,
+            "marketingEffectiveness" => business.marketing_effectiveness,
+            "demandBoost" => business.demand_boost,
+            "revPerSecFlag" => business.rev_per_sec_flag,
+            "avgRev" => business.avg_rev,
+            "autoClipperFlag" => business.clipper_flag,
+            "clipmakerLevel" => business.clipper_level,
+            "clipperBoost" => business.clipper_boost,
+            "clipperCost" => business.clipper_cost,
+            "megaClipperFlag" => business.mega_clipper_flag,
+            "megaClipperLevel" => business.mega_clipper_level,
+            "megaClipperBoost" => business.mega_clipper_boost,
+            "megaClipperCost" => business.mega_clipper_cost,
+            "prevClips" => business.prev_clips,
+            "clipRateTemp" => business.clip_rate_temp,
+            "clipRate" => business.clip_rate,
+            "unusedClips" => business.unused_clips,
+
+            "wirePriceTimer" => wire.price_timer,
+            "wireBasePrice" => wire.base_price,
+            "wirePriceCounter" => wire.price_counter,
+            "wireCost" => wire.cost,
+            "wire" => wire.count,
+            "wireSupply" => wire.supply,
+            "wirePurchase" => wire.purchase,
+            "wireBuyerFlag" => wire.buyer_flag,
+            "wireBuyerStatus" => wire.buyer_status,
+            "wireProductionFlag" => wire.production_flag,
+
+            "compFlag" => computational.comp_flag,
+            "trust" => computational.trust,
+            "processors" => computational.processors,
+            "memory" => computational.memory,
+            "operations" => computational.operations,
+            "creativity" => computational.creativity,
+            "standardOps" => computational.standard_ops,
+            "tempOps" => computational.temp_ops,
+            "opFade" => computational.op_fade,
+            "nextTrust" => computational.next_trust,
+            "creativityCounter" => computational.creativity_counter,
+            "creativitySpeed" => computational.creativity_speed,
+            "creativityOn" => computational.creativity_flag,
+
+            "projectsFlag" => projects.flag,
+            "bribe" => projects.bribe,
+
+            "qFlag" => qchips.q_flag,
+            "qChipCost" => qchips.qchip_cost,
+
+            "stockID" => investments.stock_index,
+            "maxPort" => investments.max_port,
+            "investLevel" => investments.invest_level,
+            "investUpgradeCost" => investments.invest_upgrade_cost,
+            "stockGainThreshold" => investments.stock_gain_threshold,
+            "bankroll" => investments.bankroll,
+            "ledger" => investments.ledger,
+            "sellDelay" => investments.sell_delay,
+            "investmentEngineFlag" => investments.engine_flag,
+
+            "strategyEngineFlag" => strategy.engine_flag,
+            "yomi" => strategy.yomi,
+            "yomiBoost" => strategy.yomi_boost,
+            "tourneyCost" => strategy.tourney_cost,
+            "autoTourneyFlag" => strategy.auto_tourney_flag,
+            "autoTourneyStatus" => strategy.auto_tourney_status,
+            "aa" => strategy.grid.aa,
+            "ab" => strategy.grid.ab,
+            "ba" => strategy.grid.ba,
+            "bb" => strategy.grid.bb,
+
+            "storedPower" => factory.stored_power,
+            "harvesterFlag" => factory.harvester_flag,
+            "wireDroneFlag" => factory.wire_drone_flag,
+            "factoryFlag" => factory.factory_flag,
+            "momentum" => factory.momentum,
+            "swarmFlag" => factory.swarm_flag,
+            "swarmGifts" => factory.swarm_gifts,
+            "sliderPos" => factory.swarm_slider,
+            "giftCountdown" => factory.gift_countdown,
+            "boredomFlag" => factory.boredom_flag,
+            "boredomLevel" => factory.boredom_level,
+            "boredomMsg" => factory.boredom_msg,
+            "disorgFlag" => factory.disorg_flag,
+            "disorgCounter" => factory.disorg_counter,
+            "disorgMsg" => factory.disorg_msg,
+            "factoryLevel" => factory.factory_level,
+            "factoryRate" => factory.factory_rate,
+            "factoryBoost" => factory.factory_boost,
+            "factoryBill" => factory.factory_bill,
+            "factoryCost" => factory.factory_cost,
+            "droneBoost" => factory.drone_boost,
+            "dronePowerRate" => factory.drone_power_rate,
+            "harvesterLevel" => factory.harvester_level,
+            "harvesterRate" => factory.harvester_rate,
+            "harvesterBill" => factory.harvester_bill,
+            "harvesterCost" => factory.harvester_cost,
+            "wireDroneLevel" => factory.wire_drone_level,
+            "wireDroneRate" => factory.wire_drone_rate,
+            "wireDroneBill" => factory.wire_drone_bill,
+            "wireDroneCost" => factory.wire_drone_cost,
+            "farmLevel" => factory.farm_level,
+            "farmBill" => factory.farm_bill,
+            "farmCost" => factory.farm_cost,
+            "farmRate" => factory.farm_rate,
+            "factoryPowerRate" => factory.factory_power_rate,
+            "batteryLevel" => factory.battery_level,
+            "batteryBill" => factory.battery_bill,
+            "batteryCost" => factory.battery_cost,
+            "powMod" => factory.pow_mod,
+
+            "availableMatter" => space.available_matter,
+            "foundMatter" => space.found_matter,
+            "acquiredMatter" => space.acquired_matter,
+            "processedMatter" => space.processed_matter,
+            "probeCount" => space.probe_count,
+            "probeLaunchLevel" => space.probe_launch_level,
+            "probeTrust" => space.probe_trust,
+            "probeTrustCost" => space.probe_trust_cost,
+            "probeUsedTrust" => space.probe_used_trust,
+            "maxTrust" => space.max_trust,
+            "maxTrustCost" => space.max_trust_cost,
+            "probeSpeed" => space.probe_speed,
+            "probeNav" => space.probe_nav,
+            "probeRep" => space.probe_rep,
+            "probeHaz" => space.probe_haz,
+            "probeFac" => space.probe_fac,
+            "probeHarv" => space.probe_harv,
+            "probeWire" => space.probe_wire,
+            "partialProbeHaz" => space.partial_probe_haz,
+            "partialProbeSpawn" => space.partial_probe_spawn,
+            "probesLostHaz" => space.probes_lost_haz,
+            "probesLostDrift" => space.probes_lost_drift,
+            "probesLostCombat" => space.probes_lost_combat,
+            "driftersKilled" => space.drifters_killed,
+            "drifterCount" => space.drifter_count,
+            "probeDescendents" => space.probe_descendents,
+
+            "battleFlag" => combat.battle_flag,
+            "battleNameFlag" => combat.battle_name_flag,
+            "attackSpeedFlag" => combat.attack_speed_flag,
+            "unitSize" => combat.unit_size,
+            "battleClock" => combat.battle_clock,
+            "battleEndDelay" => combat.battle_end_delay,
+            "battleEndTimer" => combat.battle_end_timer,
+            "masterBattleClock" => combat.master_battle_clock,
+            "honor" => combat.honor,
+            "honorCount" => combat.honor_count,
+            "honorReward" => combat.honor_reward,
+            "bonusHonor" => combat.bonus_honor,
+        }
+
+        if let Some(fib2) = save.get("fib2") {
+            pc.computational.fib[0] = fib2;
+        }
+        if let Some(fib1) = save.get("fib1") {
+            pc.computational.fib[1] = fib1;
+        }
+        pc.factory.update_harvester_drone_prices();
+        pc.factory.update_wire_drone_prices();
+        pc.factory.update_farm_prices();
+        pc.factory.update_battery_prices();
+        pc.progression = Progression::from_flags(
+            save.get("humanFlag").unwrap_or(true),
+            save.get("spaceFlag").unwrap_or(false),
+            pc.milestones,
+        );
+        // credits are worked out from `endTimer6`
+        pc.milestones = pc.milestones.min(15);
+        // the original only has a single `boredomLevel`, shared by the swarm and space exploration
+        pc.space.boredom_level = pc.factory.boredom_level as Float;
+
+        if let Some(dismantle) = save.get::<u8>("dismantle") {
+            pc.end.dismantle = match dismantle {
+                1 => Dismantle::Probes,
+                2 => Dismantle::Swarm,
+                3 => Dismantle::Factories,
+                4 => Dismantle::Strategy,
+                5 => Dismantle::Quantum,
+                6 => Dismantle::Processors,
+                7.. => Dismantle::Memory,
+                0 => Dismantle::None,
+            };
+        }
+        if let Some(status) = save.get::<u8>("swarmStatus") {
+            pc.factory.swarm_status = match status {
+                0 => SwarmStatus::Active,
+                1 => SwarmStatus::Hungry,
+                2 => SwarmStatus::Confused,
+                3 => SwarmStatus::Bored,
+                4 => SwarmStatus::Cold,
+                5 => SwarmStatus::Disorganized,
+                6 => SwarmStatus::Sleeping,
+                8 => SwarmStatus::Lonely,
+                9 => SwarmStatus::NoResponse,
+                _ => SwarmStatus::None,
+            };
+        }
+        if let Some(riskiness) = save.get::<u8>("riskiness") {
+            pc.investments.riskiness = match riskiness {
+                7 => Riskiness::Low,
+                5 => Riskiness::Medium,
+                _ => Riskiness::High,
+            };
+        }
+        for (key, r#move) in [("hMove", &mut pc.strategy.grid.h_move), ("vMove", &mut pc.strategy.grid.v_move)] {
+            if let Some(m) = save.get::<u8>(key) {
+                *r#move = if m == 2 { Move::B } else { Move::A };
+            }
+        }
+
+        if let Some(Value::Array(tracker)) = save.game.get("incomeTracker") {
+            pc.business.income_tracker = tracker.iter().filter_map(Float::from_js).collect();
+        }
+        if let Some(Value::Array(chips)) = save.game.get("qChips") {
+            pc.qchips.activated = 0;
+            for (chip, value) in pc.qchips.chips.iter_mut().zip(chips) {
+                *chip = value.get("value").and_then(Float::from_js).unwrap_or_default();
+                if value.get("active").and_then(bool::from_js).unwrap_or_default() {
+                    pc.qchips.activated += 1;
+                }
+            }
+        }
+        if let Some(Value::Array(stocks)) = save.game.get("stocks") {
+            pc.investments.stocks = stocks.iter()
+                .filter_map(|stock| Some(Stock {
+                    symbol: ArrayString::from(stock.get("symbol")?.as_str()?).ok()?,
+                    price: stock.get("price").and_then(Float::from_js)?,
+                    amount: stock.get("amount").and_then(u32::from_js)?,
+                    profit: stock.get("profit").and_then(Float::from_js).unwrap_or_default(),
+                }))
+                .take(pc.investments.max_port)
+                .collect();
+        }
+        if let Some(Value::Array(numbers)) = save.game.get("battleNumbers") {
+            for (number, value) in pc.combat.battle_numbers.iter_mut().zip(numbers) {
+                *number = u32::from_js(value).unwrap_or_default();
+            }
+        }
+        if let Some(Value::String(title)) = save.game.get("threnodyTitle")
+            && let Ok(title) = ArrayString::from(title)
+        {
+            pc.combat.threnody_title = title;
+        }
+        pc.combat.battle_name = match save.game.get("battleName") {
+            Some(Value::String(name)) if pc.combat.battle_name_flag => {
+                ArrayString::from(name).map_or(BattleName::Id(0), BattleName::Name)
+            },
+            _ => BattleName::Id(save.get("battleID").unwrap_or_default()),
+        };
+
+        if let Some(prestige_u) = save.prestige("prestigeU") {
+            pc.business.prestige_u = prestige_u;
+        }
+        if let Some(prestige_s) = save.prestige("prestigeS") {
+            pc.computational.prestige_s = prestige_s;
+        }
+
+        // projects: `flag` is set by the effect, `uses` goes down when the project gets displayed
+        for (i, project) in PROJECTS.iter().enumerate() {
+            let js_id = js_project_id(project);
+            let active = save.projects_active.iter().any(|id| id.as_str() == Some(&js_id));
+            let flag = save.projects_flags.get(i).and_then(bool::from_js).unwrap_or_default();
+            let used = save.projects_uses.get(i).and_then(u32::from_js).is_some_and(|uses| uses == 0);
+
+            *pc.projects.status_mut(project) = match (active, flag, used) {
+                (true, _, _) => ProjectStatus::Buyable,
+                (false, true, _) | (false, false, true) => ProjectStatus::Bought,
+                (false, false, false) => ProjectStatus::Locked,
+            };
+            if active {
+                pc.projects.buyable_projects.push((pc.ticks, project));
+            }
+        }
+
+        // strats: `allStrats[i].active`, the original order matches `ALL_STRATS`
+        if !save.strats_active.is_empty() {
+            pc.strategy.strats.clear();
+            for (strat, active) in ALL_STRATS.iter().zip(&save.strats_active) {
+                if bool::from_js(active).unwrap_or_default() {
+                    pc.strategy.strats.push((strat, 0));
+                }
+            }
+        }
+        if let Some(pick) = save.get::<usize>("pick")
+            && let Some(strat) = ALL_STRATS.get(pick)
+            && pc.strategy.strats.iter().any(|(s, _)| *s == strat)
+        {
+            pc.strategy.pick = strat;
+        }
+
+        Ok(pc)
+    }
+}
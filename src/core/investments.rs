@@ -7,7 +7,7 @@ use arrayvec::ArrayString;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
-use crate::{core::{Float, PaperClips}, rng::PCRng};
+use crate::{core::{Float, PaperClips}, event::GameEvent, rng::PCRng};
 
 pub const MAX_STOCKS: usize = 5;
 pub const UPDATE_STOCK_SHOP: Duration = Duration::from_millis(1000);
@@ -46,7 +46,7 @@ impl Riskiness {
 
 pub type Symbol = ArrayString<4>;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Stock {
     // these two aren't used
     // pub id: usize,
@@ -64,7 +64,8 @@ impl Stock {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Investments {
     pub stocks: VecDeque<Stock>,
 
@@ -150,7 +151,8 @@ impl PaperClips {
         *stock_gain_threshold += 0.01;
         *invest_upgrade_cost = (((*invest_level + 1) as Float).powf(f64::consts::E as Float) * 100.0).floor();
 
-        self.console.push(format!("Investment engine upgraded, expected profit/loss ratio now {stock_gain_threshold:.2?}"));
+        let message = format!("Investment engine upgraded, expected profit/loss ratio now {stock_gain_threshold:.2?}");
+        self.log(message);
     }
     pub fn invest_deposit(&mut self) {
         let Investments { bankroll, ledger, .. } = &mut self.investments;
@@ -218,7 +220,9 @@ impl PaperClips {
         let Investments { stocks, bankroll, .. } = &mut self.investments;
         
         if let Some(stock) = stocks.pop_front() {
-            *bankroll += stock.total();
+            let total = stock.total();
+            *bankroll += total;
+            self.emit(GameEvent::StockSold { symbol: stock.symbol, total });
         }
     }
     pub fn update_stocks(&mut self) {
@@ -1,13 +1,20 @@
 #![allow(clippy::unnecessary_cast)]
 
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
-use crate::{business::Business, combat::Combat, core::{computational::Computational, console::Console, investments::Investments, qchips::QChips, wire::Wire}, end::End, factory::Factory, project::{PROJECT_35, Projects}, rng::PCRng, sounds::Threnody, space::{Space, TOTAL_MATTER}, strategy::Strategy, util::ticks_10ms};
+use serde::{Deserialize, Serialize};
+
+use crate::{business::Business, combat::Combat, core::{computational::Computational, console::Console, investments::Investments, qchips::QChips, wire::Wire}, end::{Dismantle, End}, factory::Factory, event::GameEvent, progression::Progression, project::Projects, rates::Rates, rng::PCRng, stats::Stats, sounds::Threnody, space::{Space, TOTAL_MATTER}, strategy::Strategy, util::ticks_10ms};
 
 // Can easily get changed with f128 in the future
 pub type Float = f64;
 pub type Ticks = u128;
 
+/// How often `main_tick` runs
+pub const MAIN_TICK: Duration = Duration::from_millis(10);
+/// How often `update_combat` runs (once per frame at 60fps)
+pub const COMBAT_TICK: Duration = Duration::from_millis(16);
+
 pub mod business;
 pub mod console;
 pub mod wire;
@@ -24,14 +31,30 @@ pub mod combat;
 pub mod end;
 pub mod cheat;
 pub mod rng;
-
-#[derive(Debug, Clone)] // TODO: , Serialize, Deserialize
+pub mod save;
+pub mod import;
+pub mod action;
+pub mod replay;
+pub mod rates;
+pub mod stats;
+pub mod telemetry;
+pub mod player;
+pub mod speedrun;
+pub mod warp;
+pub mod batch;
+pub mod simulation;
+pub mod progression;
+pub mod event;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PaperClips {
     pub ticks: u128,
 
-    pub milestone_flag: u8, // TODO: enum maybe?
-    pub human_flag: bool,
-    pub dismantle: u8, // TODO: enum maybe? part 2
+    pub progression: Progression,
+    /// # milestoneFlag
+    /// How many of the milestones in `milestone_check` have been reached
+    pub milestones: u8,
 
     pub console: Console,
     pub business: Business,
@@ -47,40 +70,42 @@ pub struct PaperClips {
     pub threnody: Threnody,
     pub end: End,
     pub rng: PCRng,
+    #[serde(skip)]
+    pub rates: Rates,
+    #[serde(skip)]
+    pub stats: Stats,
+    /// Events that happened since [`PaperClips::take_events`] was last called,
+    /// the [`Simulation`](simulation::Simulation) takes them after every tick
+    #[serde(skip)]
+    pub events: VecDeque<(Ticks, GameEvent)>,
 }
 
-impl Default for PaperClips {
-    fn default() -> Self {
-        Self {
-            ticks: 0,
-
-            milestone_flag: 0,
-            human_flag: true,
-            dismantle: 0,
-
-            console: Console::default(),
-            business: Business::default(),
-            wire: Wire::default(),
-            computational: Computational::default(),
-            projects: Projects::default(),
-            qchips: QChips::default(),
-            investments: Investments::default(),
-            strategy: Strategy::default(),
-            factory: Factory::default(),
-            space: Space::default(),
-            combat: Combat::default(),
-            threnody: Threnody::default(),
-            end: End::default(),
-            rng: PCRng::default(),
+impl PaperClips {
+    /// Runs a main tick and every combat update that falls into it,
+    /// so the combat keeps its own cadence without needing a clock.
+    pub fn step(&mut self) {
+        self.main_tick();
+
+        let combat_updates = |ticks: Ticks| ticks * MAIN_TICK.as_nanos() / COMBAT_TICK.as_nanos();
+        for _ in combat_updates(self.ticks - 1)..combat_updates(self.ticks) {
+            self.update_combat();
         }
     }
-}
 
-impl PaperClips {
     /// Should run once every 10ms
+    #[inline]
     pub fn main_tick(&mut self) {
+        self.main_tick_with(Self::manage_projects);
+    }
+    /// `main_tick` with the scan over every project, only kept for the benchmark
+    #[doc(hidden)]
+    pub fn main_tick_scan(&mut self) {
+        self.main_tick_with(Self::manage_projects_scan);
+    }
+    #[inline(always)]
+    fn main_tick_with(&mut self, manage_projects: impl FnOnce(&mut Self)) {
         self.ticks += 1;
-        
+
         self.milestone_check();
         self.button_update();
 
@@ -88,7 +113,7 @@ impl PaperClips {
             self.computational.calculate_operations();
         }
 
-        if self.human_flag {
+        if self.human_flag() {
             self.calculate_trust();
         }
 
@@ -97,7 +122,7 @@ impl PaperClips {
         }
 
         // update_stats();
-        self.manage_projects();
+        manage_projects(self);
         // why does it happen twice lmao
         self.milestone_check();
 
@@ -115,18 +140,18 @@ impl PaperClips {
         // Stock Report
         if self.investments.engine_flag && self.ticks.is_multiple_of(ticks_10ms(Duration::from_secs(100))) {
             let r = self.investments.ledger + self.investments.port_total();
-            self.console.push(format!("Lifetime investment revenue report: ${r:.2}"));
+            self.log(format!("Lifetime investment revenue report: ${r:.2}"));
         }
 
         // WireBuyer
-        if self.human_flag && self.wire.buyer_flag && self.wire.buyer_status && self.wire.count <= 1.0 {
+        if self.human_flag() && self.wire.buyer_flag && self.wire.buyer_status && self.wire.count <= 1.0 {
             self.buy_wire();
         }
 
         // First, Explore
         self.space.explore_universe();
         // Then, Drones
-        if !self.human_flag && !self.space.space_flag {
+        if !self.human_flag() && !self.space_flag() {
             // update_drone_buttons(); // This is managed by the GUI
         }
 
@@ -141,13 +166,13 @@ impl PaperClips {
         } else {
             1.0
         };
-        if self.dismantle < 4 {
+        if self.end.dismantle < Dismantle::Strategy {
             self.clip_click(self.factory.pow_mod * fbst * self.factory.factory_level.floor() * self.factory.factory_rate);
         }
 
         // Then Other Probe Functions
-        if self.space.space_flag {
-            self.space.probe_count = self.space.probe_count.min(0.0);
+        if self.space_flag() {
+            self.space.probe_count = self.space.probe_count.max(0.0);
             self.encounter_hazards();
             self.spawn_factories();
             self.spawn_harvesters();
@@ -159,13 +184,13 @@ impl PaperClips {
         }
 
         // Auto-Clipper
-        if self.dismantle < 4 {
+        if self.end.dismantle < Dismantle::Strategy {
             self.clip_click(self.business.clipper_boost * (self.business.clipper_level / 100.0));
             self.clip_click(self.business.mega_clipper_boost * (self.business.mega_clipper_level * 5.0));
         }
 
         // Demand Curve 
-        if self.human_flag {
+        if self.human_flag() {
             // put everything into this function
             self.business.update_demand();
         }
@@ -199,13 +224,16 @@ impl PaperClips {
 
         // Small fixes that aren't in the original code
         if self.console.messages.is_empty() {
-            self.console.push("Welcome to Universal Paperclips");
+            self.log("Welcome to Universal Paperclips");
         }
+
+        self.track_rates();
+        self.track_stats();
     }
 
     /// Should run once every 1000ms
     pub fn update_stock_shop_tick(&mut self) {
-        if self.human_flag {
+        if self.human_flag() {
             self.stock_shop();
             self.business.calculate_rev();
         }
@@ -214,7 +242,7 @@ impl PaperClips {
     /// Should run once every 2500ms
     pub fn update_stocks_tick(&mut self) {
         self.investments.sell_delay += 1;
-        if self.human_flag && !self.investments.stocks.is_empty() {
+        if self.human_flag() && !self.investments.stocks.is_empty() {
             if self.investments.sell_delay >= 5 && self.rng.random_bool_no_best(0.3) {
                 self.sell_stock();
                 self.investments.sell_delay = 0;
@@ -228,7 +256,7 @@ impl PaperClips {
         // Wire Price Fluctuation
         self.wire.adjust_wire_price(&mut self.rng);
 
-        if self.human_flag {
+        if self.human_flag() {
             // Sales Calculator
             if self.rng.random_bool((self.business.demand as f64 / 100.0).clamp(0.0, 1.0), true) {
                 self.sell_clips(self.business.scaled_demand().floor());
@@ -249,7 +277,7 @@ impl PaperClips {
             }
         }
 
-        if !self.human_flag {
+        if !self.human_flag() {
             self.investments.engine_flag = false;
             self.wire.buyer_flag = false;
         }
@@ -263,14 +291,15 @@ impl PaperClips {
         ) {
             self.computational.comp_flag = true;
             self.projects.flag = true;
-            self.console.push("Trust-Constrained Self-Modification enabled");
+            self.log("Trust-Constrained Self-Modification enabled");
         }
 
         macro_rules! milestones {
             ($([$milestone:literal] $condition:tt => $($code:block)? $($kind:ident $text:expr;)?)*) => {
                 $(
-                    if self.milestone_flag == $milestone && milestones!(@ $condition) {
-                        self.milestone_flag += 1;
+                    if self.milestones == $milestone && milestones!(@ $condition) {
+                        self.emit(GameEvent::MilestoneReached($milestone));
+                        self.milestones += 1;
                         $( $code; )?
                         $( milestones!(@ $kind $text); )?
                     }
@@ -278,9 +307,9 @@ impl PaperClips {
             };
             (@ time $text:literal) => {
                 let message = self.milestone_string($text);
-                self.console.push(message);
+                self.log(message);
             };
-            (@ text $text:literal) => { self.console.push($text); };
+            (@ text $text:literal) => { self.log($text); };
             (@ (clips($amount:expr))) => { self.business.clips >= $amount as Float };
             (@ ($condition:expr)) => { $condition };
         }
@@ -294,29 +323,33 @@ impl PaperClips {
             [3] (clips(10000)) => time "10,000 clips created";
             [4] (clips(100000)) => time "100,000 clips created";
             [5] (clips(1000000)) => time "1,000,000 clips created";
-            [6] (self.projects.is_active(PROJECT_35)) => time "Full autonomy attained";
+            [6] (self.progression >= Progression::PostHuman) => time "Full autonomy attained";
             [7] (clips(1000000000000.0)) => time "One Trillion Clips Created";
             [8] (clips(1000000000000000.0)) => time "One Quadrillion Clips Created";
             [9] (clips(1000000000000000000.0)) => time "One Quintillion Clips Created";
             [10] (clips(1000000000000000000000.0)) => time "One Sextillion Clips Created";
             [11] (clips(1000000000000000000000000.0)) => time "One Septillion Clips Created";
             [12] (clips(1000000000000000000000000000.0)) => time "One Octillion Clips Created";
-            [13] (self.space.space_flag) => time "Terrestrial resources fully utilized";
+            [13] (self.space_flag()) => time "Terrestrial resources fully utilized";
             [14] (
                 (self.business.clips >= TOTAL_MATTER)
                 || (self.space.found_matter >= TOTAL_MATTER && self.space.available_matter < 1.0 && self.wire.count < 1.0)
-            ) => time "Universal Paperclips achieved";
+            ) => { self.advance(Progression::Endgame); } time "Universal Paperclips achieved";
         }
     }
 
     pub fn reset(&mut self) {
         let prestige_u = self.business.prestige_u;
         let prestige_s = self.computational.prestige_s;
+        let rng = self.rng;
+        let ship_cap = self.combat.ship_cap;
 
         *self = Default::default();
 
         self.business.prestige_u = prestige_u;
         self.computational.prestige_s = prestige_s;
+        self.rng = rng;
+        self.combat.ship_cap = ship_cap;
     }
 
     pub fn reset_prestige(&mut self) {
@@ -0,0 +1,261 @@
+use crate::{
+    Float, PaperClips, Ticks,
+    action::Action,
+    investments::Riskiness,
+    project::{PROJECT_147, Project},
+};
+
+/// Something that plays the game through [`Action`]s, like a bot or a recorded run.
+///
+/// It looks at the game after every tick, the actions it returns get applied in order.
+/// Players don't have to keep track of what their earlier actions spent,
+/// the ones that can't be afforded anymore simply fail.
+pub trait Player {
+    fn name(&self) -> &str;
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action>;
+}
+
+impl PaperClips {
+    /// Steps the game, then applies what `player` wants to do, skipping the actions that fail
+    pub fn step_with(&mut self, player: &mut (impl Player + ?Sized)) {
+        self.step();
+        for action in player.play(self) {
+            let _ = self.apply(action);
+        }
+    }
+}
+
+/// Several players taking turns on every tick, in order
+#[derive(Default)]
+pub struct Team {
+    pub players: Vec<Box<dyn Player + Send>>,
+}
+
+impl Team {
+    /// Every reference bot, enough to get through the human phase on its own
+    pub fn reference() -> Self {
+        Self {
+            players: vec![
+                Box::new(ClipperBuyer::default()),
+                Box::new(PriceOptimiser::default()),
+                Box::new(ProjectBuyer::default()),
+                Box::new(TourneyRunner),
+                Box::new(Investor::default()),
+            ],
+        }
+    }
+}
+
+impl Player for Team {
+    fn name(&self) -> &str {
+        "Team"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        self.players.iter_mut().flat_map(|player| player.play(pc)).collect()
+    }
+}
+
+/// Keeps the clips selling about as fast as they get made, at the highest price that allows it
+#[derive(Debug, Clone, Copy)]
+pub struct PriceOptimiser {
+    /// How often the price gets adjusted
+    pub interval: Ticks,
+    /// How many seconds of production can be left unsold before the price goes down
+    pub stock_secs: Float,
+}
+
+impl Default for PriceOptimiser {
+    fn default() -> Self {
+        Self { interval: 50, stock_secs: 10.0 }
+    }
+}
+
+impl Player for PriceOptimiser {
+    fn name(&self) -> &str {
+        "Price optimiser"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        if !pc.human_flag() || !pc.ticks.is_multiple_of(self.interval) {
+            return Vec::new()
+        }
+        let b = &pc.business;
+        // sales get attempted every 100ms, see `update_wire_price_and_demand_tick`
+        let sales_per_sec = (b.demand / 100.0).clamp(0.0, 1.0) * b.scaled_demand().floor() * 10.0;
+        let made_per_sec = b.clip_rate.max(1.0);
+
+        if b.unsold_clips > made_per_sec * self.stock_secs && sales_per_sec < made_per_sec {
+            vec![Action::LowerPrice]
+        } else if b.unsold_clips < made_per_sec && sales_per_sec > made_per_sec * 1.5 {
+            vec![Action::RaisePrice]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Makes clips by hand, buys wire, and spends the rest on AutoClippers, MegaClippers and marketing
+#[derive(Debug, Clone, Copy)]
+pub struct ClipperBuyer {
+    /// Ticks between hand-made clips, the clicking stops once 10 AutoClippers are running
+    pub click_interval: Ticks,
+    /// Wire gets bought when there is less than this share of a spool left
+    pub wire_reserve: Float,
+    /// Marketing counts as this share of its cost when picking the cheapest purchase
+    pub ad_weight: Float,
+}
+
+impl Default for ClipperBuyer {
+    fn default() -> Self {
+        Self { click_interval: 10, wire_reserve: 0.2, ad_weight: 0.1 }
+    }
+}
+
+impl Player for ClipperBuyer {
+    fn name(&self) -> &str {
+        "AutoClipper & marketing buyer"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if !pc.human_flag() {
+            return actions
+        }
+        let b = &pc.business;
+        let mut funds = b.funds;
+
+        if b.clipper_level < 10.0 && pc.ticks.is_multiple_of(self.click_interval) && pc.wire.count >= 1.0 {
+            actions.push(Action::MakePaperclip);
+        }
+        if pc.wire.count < pc.wire.supply * self.wire_reserve && !(pc.wire.buyer_flag && pc.wire.buyer_status) && funds >= pc.wire.cost {
+            actions.push(Action::BuyWire);
+            funds -= pc.wire.cost;
+        }
+
+        // the cheapest thing that still leaves enough for a spool of wire
+        let options = [
+            (b.clipper_flag, b.clipper_cost, 1.0, Action::MakeClipper),
+            (b.mega_clipper_flag, b.mega_clipper_cost, 1.0, Action::MakeMegaClipper),
+            (true, b.ad_cost, self.ad_weight, Action::BuyAds),
+        ];
+        if let Some(&(_, cost, _, action)) = options
+            .iter()
+            .filter(|(unlocked, ..)| *unlocked)
+            .min_by(|a, b| (a.1 * a.2).total_cmp(&(b.1 * b.2)))
+            && funds - cost >= pc.wire.cost
+        {
+            actions.push(action);
+        }
+
+        actions
+    }
+}
+
+/// Buys every project as soon as its cost can be paid, and spends trust on processors and memory
+#[derive(Debug, Clone)]
+pub struct ProjectBuyer {
+    /// Projects that never get bought, by default accepting the Drift King's offer
+    pub skip: Vec<&'static Project>,
+    /// How much memory gets added for every processor
+    pub memory_per_processor: u32,
+}
+
+impl Default for ProjectBuyer {
+    fn default() -> Self {
+        Self { skip: vec![&PROJECT_147], memory_per_processor: 2 }
+    }
+}
+
+impl Player for ProjectBuyer {
+    fn name(&self) -> &str {
+        "Project buyer"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        if let Some(&(_, project)) = pc.projects.buyable_projects
+            .iter()
+            .find(|(_, project)| !self.skip.contains(project) && (project.cost.1)(pc))
+        {
+            actions.push(Action::BuyProject(project));
+        }
+
+        let c = &pc.computational;
+        let can_add = match pc.human_flag() {
+            true => c.trust > (c.processors + c.memory) as i32,
+            false => pc.factory.swarm_gifts >= 1.0,
+        };
+        if c.comp_flag && can_add {
+            actions.push(match c.memory < c.processors * self.memory_per_processor {
+                true => Action::AddMemory,
+                false => Action::AddProcessor,
+            });
+        }
+
+        actions
+    }
+}
+
+/// Runs a tournament whenever the operations allow it, picking the strat that did best in the last one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TourneyRunner;
+
+impl Player for TourneyRunner {
+    fn name(&self) -> &str {
+        "Tournament runner"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        let s = &pc.strategy;
+        if !s.engine_flag || s.tourney_in_prog || pc.computational.operations < s.tourney_cost {
+            return Vec::new()
+        }
+        let mut actions = vec![Action::NewTourney];
+        if let Some(&(best, _)) = s.strats.iter().max_by_key(|(_, score)| *score) {
+            actions.push(Action::PickStrat(best));
+        }
+        actions.push(Action::RunTourney);
+        actions
+    }
+}
+
+/// Deposits the funds in the investment engine, and withdraws the cash half an interval later
+#[derive(Debug, Clone, Copy)]
+pub struct Investor {
+    /// Ticks between two deposits, 0 counts as 1
+    pub interval: Ticks,
+    /// Smaller funds aren't worth investing
+    pub min_deposit: Float,
+    pub riskiness: Riskiness,
+}
+
+impl Default for Investor {
+    fn default() -> Self {
+        Self { interval: 2000, min_deposit: 10_000.0, riskiness: Riskiness::Medium }
+    }
+}
+
+impl Player for Investor {
+    fn name(&self) -> &str {
+        "Investor"
+    }
+    fn play(&mut self, pc: &PaperClips) -> Vec<Action> {
+        let i = &pc.investments;
+        let mut actions = Vec::new();
+        if !pc.human_flag() || !i.engine_flag {
+            return actions
+        }
+
+        if i.riskiness != self.riskiness {
+            actions.push(Action::SetRiskiness(self.riskiness));
+        }
+        // leaving enough yomi for the projects
+        if pc.strategy.yomi >= i.invest_upgrade_cost * 2.0 {
+            actions.push(Action::InvestUpgrade);
+        }
+        let interval = self.interval.max(1);
+        match pc.ticks % interval {
+            0 if pc.business.funds >= self.min_deposit => actions.push(Action::InvestDeposit),
+            t if t == interval / 2 && i.bankroll > 0.0 => actions.push(Action::InvestWithdraw),
+            _ => {},
+        }
+        actions
+    }
+}
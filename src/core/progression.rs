@@ -0,0 +1,71 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{PaperClips, event::GameEvent};
+
+/// The phases of a game, in order. The game only ever moves forward, see [`PaperClips::advance`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Progression {
+    /// Selling clips to humans
+    #[default]
+    Human,
+    /// After the HypnoDrones got released, the Earth is turned into clips
+    PostHuman,
+    /// After launching the probes, the universe is turned into clips
+    Space,
+    /// All the matter of the universe is clips: the Drift King's offer and the final dismantling
+    Endgame,
+    /// Everything got dismantled and the credits are rolling
+    Credits,
+}
+
+impl Progression {
+    /// Works out the phase from the flags of the original game
+    pub const fn from_flags(human_flag: bool, space_flag: bool, milestone_flag: u8) -> Self {
+        match (human_flag, space_flag, milestone_flag) {
+            (_, _, 16..) => Self::Credits,
+            (_, _, 15) => Self::Endgame,
+            (_, true, _) => Self::Space,
+            (false, false, _) => Self::PostHuman,
+            (true, false, _) => Self::Human,
+        }
+    }
+}
+
+impl Display for Progression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Human => "Human",
+            Self::PostHuman => "Post-human",
+            Self::Space => "Space",
+            Self::Endgame => "Endgame",
+            Self::Credits => "Credits",
+        })
+    }
+}
+
+impl PaperClips {
+    /// # humanFlag
+    #[inline]
+    pub fn human_flag(&self) -> bool {
+        self.progression == Progression::Human
+    }
+    /// # spaceFlag
+    #[inline]
+    pub fn space_flag(&self) -> bool {
+        self.progression >= Progression::Space
+    }
+
+    /// Moves the game to `to`, emitting [`GameEvent::Progressed`].
+    ///
+    /// Returns `false` and does nothing if the game is already there or further along.
+    pub fn advance(&mut self, to: Progression) -> bool {
+        if to <= self.progression {
+            return false
+        }
+        let from = std::mem::replace(&mut self.progression, to);
+        self.emit(GameEvent::Progressed { from, to });
+        true
+    }
+}
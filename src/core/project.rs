@@ -1,19 +1,31 @@
-use std::{borrow::Cow, time::Instant};
+use std::borrow::Cow;
 
-use crate::{Float, PaperClips, combat::THRENODY_START, computational::MEM_SIZE, space::PROBE_COST, strategy::strategies::*, util::powf};
+use crate::{Float, PaperClips, Ticks, combat::THRENODY_START, computational::MEM_SIZE, end::Dismantle, event::GameEvent, progression::Progression, space::PROBE_COST, strategy::strategies::*, util::powf};
 use ProjectStatus::*;
 use arrayvec::ArrayVec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
 
 pub const DRIFT_KING_MESSAGE_COST: Float = 1.0;
+/// The projects that read out the Drift King's messages, in order
+pub const DRIFT_KING_MESSAGES: [&Project; 7] = [&PROJECT_140, &PROJECT_141, &PROJECT_142, &PROJECT_143, &PROJECT_144, &PROJECT_145, &PROJECT_146];
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Projects {
     pub flag: bool,
 
-    pub buyable_projects: ArrayVec<(Instant, &'static Project), PROJECTS_COUNT>,
-    pub statuses: [ProjectStatus; PROJECTS_COUNT],
+    /// The tick at which each project became buyable
+    pub buyable_projects: ArrayVec<(Ticks, &'static Project), PROJECTS_COUNT>,
+    /// Only changed through [`Projects::status_mut`], which keeps `watched` up to date
+    #[serde(with = "crate::save::padded_array")]
+    statuses: [ProjectStatus; PROJECTS_COUNT],
 
     pub bribe: Float,
+
+    /// The locked projects whose `requires` are all bought, the only ones `manage_projects` looks at.
+    /// `None` when it needs to be rebuilt, after a project got bought or a status changed.
+    #[serde(skip)]
+    watched: Option<Vec<usize>>,
 }
 
 impl Default for Projects {
@@ -25,6 +37,8 @@ impl Default for Projects {
             statuses: PROJECTS_STATUSES,
 
             bribe: 1000000.0,
+
+            watched: None,
         }
     }
 }
@@ -35,36 +49,89 @@ impl Projects {
         self.statuses[project.as_ref().index] == Bought
     }
     #[inline]
+    pub fn status(&self, project: impl AsRef<Project>) -> ProjectStatus {
+        self.statuses[project.as_ref().index]
+    }
+    /// Indexed like `PROJECTS`
+    #[inline]
+    pub fn statuses(&self) -> &[ProjectStatus; PROJECTS_COUNT] {
+        &self.statuses
+    }
+    /// The watch list gets rebuilt on the next `manage_projects`
+    #[inline]
     pub fn status_mut(&mut self, project: impl AsRef<Project>) -> &mut ProjectStatus {
+        self.watched = None;
         &mut self.statuses[project.as_ref().index]
     }
+    /// Makes the next `manage_projects` rebuild the watch list
+    #[inline]
+    pub fn reset_watch_list(&mut self) {
+        self.watched = None;
+    }
     #[inline]
     pub fn toth_flag(&self) -> bool {
         self.is_active(PROJECT_18)
     }
+    /// The locked projects that could get triggered, in order
+    pub fn watch_list(&self) -> Vec<usize> {
+        PROJECTS
+            .iter()
+            .filter(|project| self.statuses[project.index] == Locked && project.requires.iter().all(|&i| self.statuses[i] == Bought))
+            .map(|project| project.index)
+            .collect()
+    }
 }
 
 impl PaperClips {
+    /// Only the triggers of the watched projects get called, the statuses they depend on are
+    /// checked once when the watch list is rebuilt rather than on every tick.
+    ///
+    /// Only `requires` gets indexed: the triggers of the watched projects still run every tick,
+    /// nothing keeps track of which resources they read.
     pub fn manage_projects(&mut self) {
+        let mut watched = self.projects.watched.take().unwrap_or_else(|| self.projects.watch_list());
+        let mut triggered = false;
+        for &i in &watched {
+            let project = &PROJECTS[i];
+            if (project.trigger)(self) {
+                self.projects.buyable_projects.push((self.ticks, project));
+                *self.projects.status_mut(project) = ProjectStatus::Buyable;
+                triggered = true;
+            }
+        }
+        if triggered {
+            watched.retain(|&i| self.projects.statuses[i] == Locked);
+        }
+        self.projects.watched = Some(watched);
+    }
+    /// Checks every locked project like `manage_projects` used to, only kept for the benchmark
+    #[doc(hidden)]
+    pub fn manage_projects_scan(&mut self) {
         for (i, status) in self.projects.statuses.into_iter().enumerate() {
             let project = &PROJECTS[i];
-            if status == Locked && (project.trigger)(self) {
-                self.projects.buyable_projects.push((Instant::now(), project));
-                self.projects.statuses[i] = ProjectStatus::Buyable;
+            if status == Locked && project.requires.iter().all(|&r| self.projects.statuses[r] == Bought) && (project.trigger)(self) {
+                self.projects.buyable_projects.push((self.ticks, project));
+                *self.projects.status_mut(project) = ProjectStatus::Buyable;
             }
         }
+        self.projects.reset_watch_list();
     }
     pub fn buy_project(&mut self, bpi: usize) {
         let (_, project) = self.projects.buyable_projects[bpi];
-        let pi = project.index;
         if project.cost.1(self) {
             self.projects.buyable_projects.remove(bpi);
-            self.projects.statuses[pi] = Bought;
+            // its dependents may get watched, and effects can lock projects again
+            *self.projects.status_mut(project) = Bought;
             (project.effect)(self);
+            self.emit(GameEvent::ProjectBought(project));
+            if DRIFT_KING_MESSAGES.contains(&project) {
+                self.emit(GameEvent::DriftMessage(project));
+            }
         }
     }
 }
 
+pub fn trigger_true(_: &PaperClips) -> bool { true }
 pub fn trigger_false(_: &PaperClips) -> bool { false }
 pub fn cost_false(_: &PaperClips) -> bool { false }
 pub fn effect_noop(_: &mut PaperClips) {}
@@ -90,6 +157,8 @@ pub struct Project {
     pub title: Body,
     /// # description
     pub description: Body,
+    /// Indices of the projects that have to be bought before `trigger` gets called, the first part of it in the original
+    pub requires: &'static [usize],
     /// # trigger
     pub trigger: fn(&PaperClips) -> bool,
     /// # (priceTag, cost)
@@ -99,6 +168,8 @@ pub struct Project {
 
     /// Doesn't exist in the original, but is useful
     pub index: usize,
+    /// Name of the constant, e.g. `PROJECT_10B` (`projectButton10b` in the original)
+    pub name: &'static str,
 }
 
 impl PartialEq for Project {
@@ -111,7 +182,21 @@ impl AsRef<Project> for Project {
     fn as_ref(&self) -> &Project { self }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Projects are saved by their `index` in `PROJECTS`
+impl Serialize for Project {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.index.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for &'static Project {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = usize::deserialize(deserializer)?;
+        PROJECTS.get(index).ok_or_else(|| D::Error::custom(format!("unknown project index {index}")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProjectStatus {
     /// Needs `.trigger()` to be true to get unlocked.
     #[default]
@@ -123,14 +208,16 @@ pub enum ProjectStatus {
 }
 
 macro_rules! projects {
-    ( $( $name:ident { title: $title:expr, description: $desc:expr, trigger: $trigger:expr, cost: ($cost_body:expr, $cost_fn:expr $(,)?), effect: $effect:expr $(,)? } )+ ) => {
-        projects!(@inner 0usize; [ ]; $( $name { title: $title, description: $desc, trigger: $trigger, cost: ($cost_body, $cost_fn), effect: $effect } )+ );
+    ( $( $name:ident { title: $title:expr, description: $desc:expr, $(requires: [$($req:ident),+ $(,)?],)? trigger: $trigger:expr, cost: ($cost_body:expr, $cost_fn:expr $(,)?), effect: $effect:expr $(,)? } )+ ) => {
+        projects!(@inner 0usize; [ ]; $( $name { title: $title, description: $desc, requires: [$($($req,)+)?], trigger: $trigger, cost: ($cost_body, $cost_fn), effect: $effect } )+ );
     };
-    (@inner $idx:expr; [ $($acc:ident,)* ] ; $name:ident { title: $title:expr, description: $desc:expr, trigger: $trigger:expr, cost: ($cost_body:expr, $cost_fn:expr $(,)?), effect: $effect:expr } $( $rest:tt )* ) => {
+    (@inner $idx:expr; [ $($acc:ident,)* ] ; $name:ident { title: $title:expr, description: $desc:expr, requires: [$($req:ident,)*], trigger: $trigger:expr, cost: ($cost_body:expr, $cost_fn:expr $(,)?), effect: $effect:expr } $( $rest:tt )* ) => {
         pub const $name: Project = Project {
             index: $idx,
+            name: stringify!($name),
             title: projects!(# $title),
             description: projects!(# $desc),
+            requires: &[$($req.index,)*],
             trigger: $trigger,
             cost: (projects!(# $cost_body), $cost_fn),
             effect: $effect,
@@ -156,7 +243,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 750.0;
             pc.business.clipper_boost += 0.25;
-            pc.console.push("AutoClippper performance boosted by 25%");
+            pc.log("AutoClippper performance boosted by 25%");
         },
     }
     PROJECT_2 {
@@ -171,7 +258,7 @@ projects! {
             pc.computational.trust -= 1;
             pc.wire.count += pc.wire.supply;
             *pc.projects.status_mut(PROJECT_2) = ProjectStatus::Locked;
-            pc.console.push("Budget overage approved, 1 spool of wire requisitioned from HQ");
+            pc.log("Budget overage approved, 1 spool of wire requisitioned from HQ");
         },
     }
     PROJECT_3 {
@@ -182,29 +269,31 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 1000.0;
             pc.computational.creativity_flag = true;
-            pc.console.push("Creativity unlocked (creativity increases while operations are at max)");
+            pc.log("Creativity unlocked (creativity increases while operations are at max)");
         },
     }
     PROJECT_4 {
         title: "Even Better AutoClippers",
         description: "Increases AutoClipper performance by an additional 50%",
-        trigger: |pc| pc.projects.is_active(PROJECT_1),
+        requires: [PROJECT_1],
+        trigger: trigger_true,
         cost: ("(2,500 ops)", |pc| req_operations(2500.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 2500.0;
             pc.business.clipper_boost += 0.50;
-            pc.console.push("AutoClippper performance boosted by another 50%");
+            pc.log("AutoClippper performance boosted by another 50%");
         },
     }
     PROJECT_5 {
         title: "Optimized AutoClippers",
         description: "Increases AutoClipper performance by an additional 75%",
-        trigger: |pc| pc.projects.is_active(PROJECT_4),
+        requires: [PROJECT_4],
+        trigger: trigger_true,
         cost: ("(5,000 ops)", |pc| req_operations(5000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 5000.0;
             pc.business.clipper_boost += 0.75;
-            pc.console.push("AutoClippper performance boosted by another 75%");
+            pc.log("AutoClippper performance boosted by another 75%");
         },
     }
     PROJECT_6 {
@@ -214,8 +303,8 @@ projects! {
         cost: ("(10 creat)", |pc| req_creativity(10.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 10.0;
-            pc.computational.trust += 1;
-            pc.console.push("There was an AI made of dust, whose poetry gained it man's trust...");
+            pc.gain_trust(1);
+            pc.log("There was an AI made of dust, whose poetry gained it man's trust...");
         },
     }
     PROJECT_7 {
@@ -226,7 +315,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 1750.0;
             pc.wire.supply *= 1.5;
-            pc.console.push(format!("Wire extrusion technique improved, {} supply from every spool", pc.wire.supply));
+            pc.log(format!("Wire extrusion technique improved, {} supply from every spool", pc.wire.supply));
         },
     }
     PROJECT_8 {
@@ -237,7 +326,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 3500.0;
             pc.wire.supply *= 1.75;
-            pc.console.push(format!("Wire extrusion technique optimized, {} supply from every spool", pc.wire.supply));
+            pc.log(format!("Wire extrusion technique optimized, {} supply from every spool", pc.wire.supply));
         },
     }
     PROJECT_9 {
@@ -248,7 +337,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 7500.0;
             pc.wire.supply *= 2.0;
-            pc.console.push(format!("Using microlattice shapecasting techniques we now get {} supply from every spool", pc.wire.supply));
+            pc.log(format!("Using microlattice shapecasting techniques we now get {} supply from every spool", pc.wire.supply));
         },
     }
     PROJECT_10 {
@@ -259,7 +348,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 12000.0;
             pc.wire.supply *= 3.0;
-            pc.console.push(format!("Using spectral froth annealment we now get {} supply from every spool", pc.wire.supply));
+            pc.log(format!("Using spectral froth annealment we now get {} supply from every spool", pc.wire.supply));
         },
     }
     PROJECT_10B {
@@ -270,31 +359,33 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 15000.0;
             pc.wire.supply *= 11.0;
-            pc.console.push(format!("Using quantum foam annealment we now get {} supply from every spool", pc.wire.supply));
+            pc.log(format!("Using quantum foam annealment we now get {} supply from every spool", pc.wire.supply));
         },
     }
     PROJECT_11 {
         title: "New Slogan",
         description: "Improve marketing effectiveness by 50%",
-        trigger: |pc| pc.projects.is_active(PROJECT_13),
+        requires: [PROJECT_13],
+        trigger: trigger_true,
         cost: ("(25 creat, 2,500 ops)", |pc| req_operations(2500.0)(pc) && req_creativity(25.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 2500.0;
             pc.computational.creativity -= 25.0;
             pc.business.marketing_effectiveness *= 1.50;
-            pc.console.push("Clip It! Marketing is now 50% more effective");
+            pc.log("Clip It! Marketing is now 50% more effective");
         },
     }
     PROJECT_12 {
         title: "Catchy Jingle",
         description: "Double marketing effectiveness",
-        trigger: |pc| pc.projects.is_active(PROJECT_14),
+        requires: [PROJECT_14],
+        trigger: trigger_true,
         cost: ("(45 creat, 4,500 ops)", |pc| req_operations(4500.0)(pc) && req_creativity(45.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 4500.0;
             pc.computational.creativity -= 45.0;
             pc.business.marketing_effectiveness *= 2.0;
-            pc.console.push("Clip It Good! Marketing is now twice as effective");
+            pc.log("Clip It Good! Marketing is now twice as effective");
         },
     }
     PROJECT_13 {
@@ -304,9 +395,9 @@ projects! {
         cost: ("(50 creat)", |pc| req_creativity(50.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 50.0;
-            pc.computational.trust += 1;
-            pc.console.push("Lexical Processing online, TRUST INCREASED");
-            pc.console.push("'Impossible' is a word to be found only in the dictionary of fools. -Napoleon");
+            pc.gain_trust(1);
+            pc.log("Lexical Processing online, TRUST INCREASED");
+            pc.log("'Impossible' is a word to be found only in the dictionary of fools. -Napoleon");
         },
     }
     PROJECT_14 {
@@ -316,9 +407,9 @@ projects! {
         cost: ("(100 creat)", |pc| req_creativity(100.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 100.0;
-            pc.computational.trust += 1;
-            pc.console.push("Combinatory Harmonics mastered, TRUST INCREASED");
-            pc.console.push("Listening is selecting and interpreting and acting and making decisions -Pauline Oliveros");
+            pc.gain_trust(1);
+            pc.log("Combinatory Harmonics mastered, TRUST INCREASED");
+            pc.log("Listening is selecting and interpreting and acting and making decisions -Pauline Oliveros");
         },
     }
     PROJECT_15 {
@@ -328,9 +419,9 @@ projects! {
         cost: ("(150 creat)", |pc| req_creativity(150.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 150.0;
-            pc.computational.trust += 1;
-            pc.console.push("The Hadwiger Problem: solved, TRUST INCREASED");
-            pc.console.push("Architecture is the thoughtful making of space. -Louis Kahn");
+            pc.gain_trust(1);
+            pc.log("The Hadwiger Problem: solved, TRUST INCREASED");
+            pc.log("Architecture is the thoughtful making of space. -Louis Kahn");
         },
     }
     // PROJECT_17 and PROJECT_16 are out of order
@@ -341,32 +432,34 @@ projects! {
         cost: ("(200 creat)", |pc| req_creativity(200.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 200.0;
-            pc.computational.trust += 1;
-            pc.console.push("The Tóth Sausage Conjecture: proven, TRUST INCREASED");
-            pc.console.push("You can't invent a design. You recognize it, in the fourth dimension. -D.H. Lawrence");
+            pc.gain_trust(1);
+            pc.log("The Tóth Sausage Conjecture: proven, TRUST INCREASED");
+            pc.log("You can't invent a design. You recognize it, in the fourth dimension. -D.H. Lawrence");
         },
     }
     PROJECT_16 {
         title: "Hadwiger Clip Diagrams",
         description: "Increases AutoClipper performance by an additional 500%",
-        trigger: |pc| pc.projects.is_active(PROJECT_15),
+        requires: [PROJECT_15],
+        trigger: trigger_true,
         cost: ("(6,000 ops)", |pc| req_operations(6000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 6000.0;
             pc.business.clipper_boost += 5.0;
-            pc.console.push("AutoClipper performance improved by 500%");
+            pc.log("AutoClipper performance improved by 500%");
         },
     }
     PROJECT_18 {
         title: "Tóth Tubule Enfolding",
         description: "Technique for assembling clip-making technology directly out of paperclips",
-        trigger: |pc| pc.projects.is_active(PROJECT_17) && !pc.human_flag,
+        requires: [PROJECT_17],
+        trigger: |pc| !pc.human_flag(),
         cost: ("(45,000 ops)", |pc| req_operations(45000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 45000.0;
             // this can be checked with `pc.project.is_active(PROJECT_18)`
             // toth_flag = true;
-            pc.console.push("New capability: build machinery out of clips");
+            pc.log("New capability: build machinery out of clips");
         },
     }
     PROJECT_19 {
@@ -376,20 +469,21 @@ projects! {
         cost: ("(250 creat)", |pc| req_creativity(250.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 250.0;
-            pc.computational.trust += 1;
-            pc.console.push("Donkey Space: mapped, TRUST INCREASED");
-            pc.console.push("Every commercial transaction has within itself an element of trust. - Kenneth Arrow");
+            pc.gain_trust(1);
+            pc.log("Donkey Space: mapped, TRUST INCREASED");
+            pc.log("Every commercial transaction has within itself an element of trust. - Kenneth Arrow");
         },
     }
     PROJECT_20 {
         title: "Strategic Modeling",
         description: "Analyze strategy tournaments to generate Yomi",
-        trigger: |pc| pc.projects.is_active(PROJECT_19),
+        requires: [PROJECT_19],
+        trigger: trigger_true,
         cost: ("(12,000 ops)", |pc| req_operations(12000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 12000.0;
             pc.strategy.engine_flag = true;
-            pc.console.push("Run tournament, pick strategy, earn Yomi based on that strategy's performance.");
+            pc.log("Run tournament, pick strategy, earn Yomi based on that strategy's performance.");
         },
     }
     PROJECT_21 {
@@ -400,7 +494,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 10000.0;
             pc.investments.engine_flag = true;
-            pc.console.push("Investment engine unlocked");
+            pc.log("Investment engine unlocked");
         },
     }
     PROJECT_22 {
@@ -411,40 +505,43 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 12000.0;
             pc.business.mega_clipper_flag = true;
-            pc.console.push("MegaClipper technology online");
+            pc.log("MegaClipper technology online");
         },
     }
     PROJECT_23 {
         title: "Improved MegaClippers",
         description: "Increases MegaClipper performance 25%",
-        trigger: |pc| pc.projects.is_active(PROJECT_22),
+        requires: [PROJECT_22],
+        trigger: trigger_true,
         cost: ("(14,000 ops)", |pc| req_operations(14000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 14000.0;
             pc.business.mega_clipper_boost += 0.25;
-            pc.console.push("MegaClipper performance increased by 25%");
+            pc.log("MegaClipper performance increased by 25%");
         },
     }
     PROJECT_24 {
         title: "Even Better MegaClippers",
         description: "Increases MegaClipper performance by an additional 50%",
-        trigger: |pc| pc.projects.is_active(PROJECT_23),
+        requires: [PROJECT_23],
+        trigger: trigger_true,
         cost: ("(17,000 ops)", |pc| req_operations(17000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 17000.0;
             pc.business.mega_clipper_boost += 0.50;
-            pc.console.push("MegaClipper performance increased by 50%");
+            pc.log("MegaClipper performance increased by 50%");
         },
     }
     PROJECT_25 {
         title: "Optimized MegaClippers",
         description: "Increases MegaClipper performance by an additional 100%",
-        trigger: |pc| pc.projects.is_active(PROJECT_24),
+        requires: [PROJECT_24],
+        trigger: trigger_true,
         cost: ("(19,500 ops)", |pc| req_operations(19500.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 19500.0;
             pc.business.mega_clipper_boost += 1.0;
-            pc.console.push("MegaClipper performance increased by 100%");
+            pc.log("MegaClipper performance increased by 100%");
         },
     }
     PROJECT_26 {
@@ -455,48 +552,51 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 7000.0;
             pc.wire.buyer_flag = true;
-            pc.console.push("WireBuyer online");
+            pc.log("WireBuyer online");
         },
     }
     PROJECT_34 {
         title: "Hypno Harmonics",
         description: "Use neuro-resonant frequencies to influence consumer behavior",
-        trigger: |pc| pc.projects.is_active(PROJECT_12),
+        requires: [PROJECT_12],
+        trigger: trigger_true,
         cost: ("(7,500 ops, 1 Trust)", |pc| req_operations(7500.0)(pc) && req_trust(1)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 7500.0;
             pc.computational.trust -= 1;
             pc.business.marketing_effectiveness *= 5.0;
-            pc.console.push("Marketing is now 5 times more effective");
+            pc.log("Marketing is now 5 times more effective");
         },
     }
     // PROJECT_70 is here randomly
     PROJECT_70 {
         title: "HypnoDrones",
         description: "Autonomous aerial brand ambassadors",
-        trigger: |pc| pc.projects.is_active(PROJECT_34),
+        requires: [PROJECT_34],
+        trigger: trigger_true,
         cost: ("(70,000 ops)", |pc| req_operations(70000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 70000.0;
-            pc.console.push("HypnoDrone tech now available...");
+            pc.log("HypnoDrone tech now available...");
         },
     }
     PROJECT_35 {
         title: "Release the HypnoDrones",
         description: "A new era of trust",
-        trigger: |pc| pc.projects.is_active(PROJECT_70),
+        requires: [PROJECT_70],
+        trigger: trigger_true,
         cost: ("(100 Trust)", |pc| req_trust(100)(pc)),
         effect: |pc| {
             pc.computational.trust = 0;
             pc.business.mega_clipper_level = 0.0;
-            pc.human_flag = false;
+            pc.advance(Progression::PostHuman);
             // nanoWire = wire; // this seems to be useless
 
             // TODO: check what the flip the `document.stuff()` do
-            pc.space.hypno_drone_event = Some(Instant::now());
+            pc.space.hypno_drone_event = Some(pc.ticks);
 
-            pc.console.push("Releasing the HypnoDrones");
-            pc.console.push("All of the resources of Earth are now available for clip production");
+            pc.log("Releasing the HypnoDrones");
+            pc.log("All of the resources of Earth are now available for clip production");
         },
     }
     // original dev doesn't know how to sort numbers
@@ -509,71 +609,76 @@ projects! {
             pc.strategy.yomi -= 3000.0;
             pc.computational.standard_ops -= 20000.0;
             pc.computational.creativity -= 500.0;
-            pc.computational.trust += 1;
-            pc.console.push("Coherent Extrapolated Volition complete, TRUST INCREASED");
+            pc.gain_trust(1);
+            pc.log("Coherent Extrapolated Volition complete, TRUST INCREASED");
         },
     }
     PROJECT_28 {
         title: "Cure for Cancer",
         description: "The trick is tricking cancer into curing itself. (+10 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_27),
+        requires: [PROJECT_27],
+        trigger: trigger_true,
         cost: ("(25,000 ops)", |pc| req_operations(25000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 25000.0;
-            pc.computational.trust += 10;
+            pc.gain_trust(10);
             pc.investments.stock_gain_threshold += 0.01;
-            pc.console.push("Cancer is cured, +10 TRUST, global stock prices trending upward");
+            pc.log("Cancer is cured, +10 TRUST, global stock prices trending upward");
         },
     }
     PROJECT_29 {
         title: "World Peace",
         description: "Pareto optimal solutions to all global conflicts. (+12 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_27),
+        requires: [PROJECT_27],
+        trigger: trigger_true,
         cost: ("(15,000 yomi, 30,000 ops)", |pc| req_yomi(15000.0)(pc) && req_operations(30000.0)(pc)),
         effect: |pc| {
             pc.strategy.yomi -= 15000.0;
             pc.computational.standard_ops -= 30000.0;
-            pc.computational.trust += 12;
+            pc.gain_trust(12);
             pc.investments.stock_gain_threshold += 0.01;
-            pc.console.push("World peace achieved, +12 TRUST, global stock prices trending upward");
+            pc.log("World peace achieved, +12 TRUST, global stock prices trending upward");
         
         },
     }
     PROJECT_30 {
         title: "Global Warming",
         description: "A robust solution to man-made climate change. (+15 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_27),
+        requires: [PROJECT_27],
+        trigger: trigger_true,
         cost: ("(4,500 yomi, 50,000 ops)", |pc| req_yomi(4500.0)(pc) && req_operations(50000.0)(pc)),
         effect: |pc| {
             pc.strategy.yomi -= 4500.0;
             pc.computational.standard_ops -= 50000.0;
-            pc.computational.trust += 15;
+            pc.gain_trust(15);
             pc.investments.stock_gain_threshold += 0.01;
-            pc.console.push("Global Warming solved, +15 TRUST, global stock prices trending upward");
+            pc.log("Global Warming solved, +15 TRUST, global stock prices trending upward");
         },
     }
     PROJECT_31 {
         title: "Male Pattern Baldness",
         description: "A cure for androgenetic alopecia. (+20 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_27),
+        requires: [PROJECT_27],
+        trigger: trigger_true,
         cost: ("(20,000 ops)", |pc| req_operations(20000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 20000.0;
-            pc.computational.trust += 20;
+            pc.gain_trust(20);
             pc.investments.stock_gain_threshold += 0.01;
-            pc.console.push("Male pattern baldness cured, +20 TRUST, Global stock prices trending upward");
-            pc.console.push("They are still monkeys");
+            pc.log("Male pattern baldness cured, +20 TRUST, Global stock prices trending upward");
+            pc.log("They are still monkeys");
         },
     }
     PROJECT_41 {
         title: "Nanoscale Wire Production",
         description: "Technique for converting matter into wire",
-        trigger: |pc| pc.projects.is_active(PROJECT_127),
+        requires: [PROJECT_127],
+        trigger: trigger_true,
         cost: ("(35,000 ops)", |pc| req_operations(35000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 35000.0;
             pc.wire.production_flag = true;
-            pc.console.push("Now capable of manipulating matter at the molecular scale to produce wire");
+            pc.log("Now capable of manipulating matter at the molecular scale to produce wire");
         },
     }
     PROJECT_37 {
@@ -584,21 +689,22 @@ projects! {
         effect: |pc| {
             pc.business.funds -= 1000000.0;
             pc.business.demand_boost *= 5.0;
-            pc.computational.trust += 1;
-            pc.console.push("Global Fasteners acquired, public demand increased x5");
+            pc.gain_trust(1);
+            pc.log("Global Fasteners acquired, public demand increased x5");
         },
     }
     PROJECT_38 {
         title: "Full Monopoly",
         description: "Establish full control over the world-wide paperclip market. (+1 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_37),
+        requires: [PROJECT_37],
+        trigger: trigger_true,
         cost: ("(3,000 yomi, $10,000,000)", |pc| req_funds(10000000.0)(pc) && req_yomi(3000.0)(pc)),
         effect: |pc| {
             pc.business.funds -= 10000000.0;
             pc.strategy.yomi -= 3000.0;
             pc.business.demand_boost *= 10.0;
-            pc.computational.trust += 1;
-            pc.console.push("Full market monopoly achieved, public demand increased x10");
+            pc.gain_trust(1);
+            pc.log("Full market monopoly achieved, public demand increased x10");
         },
     }
     PROJECT_42 {
@@ -609,57 +715,61 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 500.0;
             pc.business.rev_per_sec_flag = true;
-            pc.console.push("RevTracker online");
+            pc.log("RevTracker online");
         },
     }
     PROJECT_43 {
         title: "Harvester Drones",
         description: "Gather raw matter and prepare it for processing",
-        trigger: |pc| pc.projects.is_active(PROJECT_41),
+        requires: [PROJECT_41],
+        trigger: trigger_true,
         cost: ("(25,000 ops)", |pc| req_operations(25000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 25000.0;
             pc.factory.harvester_flag = true;
-            pc.console.push("Harvester Drone facilities online");
+            pc.log("Harvester Drone facilities online");
         },
     }
     PROJECT_44 {
         title: "Wire Drones",
         description: "Process acquired matter into wire",
-        trigger: |pc| pc.projects.is_active(PROJECT_41),
+        requires: [PROJECT_41],
+        trigger: trigger_true,
         cost: ("(25,000 ops)", |pc| req_operations(25000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 25000.0;
             pc.factory.wire_drone_flag = true;
-            pc.console.push("Wire Drone facilities online");
+            pc.log("Wire Drone facilities online");
         },
     }
     PROJECT_45 {
         title: "Clip Factories",
         description: "Large scale clip production facilities made from clips",
-        trigger: |pc| pc.projects.is_active(PROJECT_43) && pc.projects.is_active(PROJECT_44),
+        requires: [PROJECT_43, PROJECT_44],
+        trigger: trigger_true,
         cost: ("(35,000 ops)", |pc| req_operations(35000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 35000.0;
             pc.factory.factory_flag = true;
-            pc.console.push("Clip factory assembly facilities online");
+            pc.log("Clip factory assembly facilities online");
         },
     }
     PROJECT_40 {
         title: "A Token of Goodwill...",
         description: "A small gift to the supervisors. (+1 Trust)",
-        trigger: |pc| pc.human_flag && (85..100).contains(&pc.computational.trust) && pc.business.clips >= 101000000.0,
+        trigger: |pc| pc.human_flag() && (85..100).contains(&pc.computational.trust) && pc.business.clips >= 101000000.0,
         cost: ("($500,000)", |pc| req_funds(500000.0)(pc)),
         effect: |pc| {
             pc.business.funds -= 500000.0;
-            pc.computational.trust += 1;
-            pc.console.push("Gift accepted, TRUST INCREASED");
+            pc.gain_trust(1);
+            pc.log("Gift accepted, TRUST INCREASED");
         },
     }
     PROJECT_40B {
         title: "Another Token of Goodwill...",
         description: "Another small gift to the supervisors. (+1 Trust)",
-        trigger: |pc| pc.projects.is_active(PROJECT_40) && pc.computational.trust < 100,
+        requires: [PROJECT_40],
+        trigger: |pc| pc.computational.trust < 100,
         cost: (
             |pc| format!("(${})", pc.projects.bribe),
             |pc| pc.business.funds >= pc.projects.bribe,
@@ -667,17 +777,17 @@ projects! {
         effect: |pc| {
             pc.business.funds -= pc.projects.bribe;
             pc.projects.bribe *= 2.0;
-            pc.computational.trust += 1;
+            pc.gain_trust(1);
             if pc.computational.trust < 100 {
                 *pc.projects.status_mut(PROJECT_40B) = Locked;
             }
-            pc.console.push("Gift accepted, TRUST INCREASED");
+            pc.log("Gift accepted, TRUST INCREASED");
         },
     }
     PROJECT_46 {
         title: "Space Exploration",
         description: "Dismantle terrestrial facilities, and expand throughout the universe",
-        trigger: |pc| !pc.human_flag && pc.space.available_matter <= 0.0,
+        trigger: |pc| !pc.human_flag() && pc.space.available_matter <= 0.0,
         cost: (
             "(120,000 ops, 10,000,000 MW-seconds, 5 oct clips)",
             |pc| pc.computational.operations >= 120000.0 && pc.factory.stored_power >= 10000000.0 && pc.business.unused_clips >= powf(10.0, 27) * 5.0,
@@ -688,7 +798,7 @@ projects! {
             pc.business.unused_clips -= powf(10.0, 27) * 5.0;
 
             pc.space.boredom_level = 0.0;
-            pc.space.space_flag = true;
+            pc.advance(Progression::Space);
             pc.factory_reboot();
             pc.harvester_reboot();
             pc.wire_drone_reboot();
@@ -697,7 +807,7 @@ projects! {
             pc.factory.farm_level = 1;
             pc.factory.pow_mod = 1.0;
 
-            pc.console.push("Von Neumann Probes online");
+            pc.log("Von Neumann Probes online");
         },
     }
     PROJECT_50 {
@@ -708,13 +818,14 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 10000.0;
             pc.qchips.q_flag = true;
-            pc.console.push("Quantum computing online");
+            pc.log("Quantum computing online");
         },
     }
     PROJECT_51 {
         title: "Photonic Chip",
         description: "Converts electromagnetic waves into quantum operations",
-        trigger: |pc| pc.projects.is_active(PROJECT_50),
+        requires: [PROJECT_50],
+        trigger: trigger_true,
         cost: (
             |pc| format!("({:.0} ops)", pc.qchips.qchip_cost),
             |pc| pc.computational.operations >= pc.qchips.qchip_cost,
@@ -726,91 +837,98 @@ projects! {
             if (pc.qchips.activated as usize) < pc.qchips.chips.len() {
                 *pc.projects.status_mut(PROJECT_51) = Locked;
             }
-            pc.console.push("Photonic chip added");
+            pc.log("Photonic chip added");
         },
     }
     PROJECT_60 {
         title: "New Strategy: A100",
         description: "Always choose A",
-        trigger: |pc| pc.projects.is_active(PROJECT_20),
+        requires: [PROJECT_20],
+        trigger: trigger_true,
         cost: ("(15,000 ops)", |pc| req_operations(15000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 15000.0;
             pc.strategy.strats.push((&A100, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("A100 added to strategy pool");
+            pc.log("A100 added to strategy pool");
         },
     }
     PROJECT_61 {
         title: "New Strategy: B100",
         description: "Always choose B",
-        trigger: |pc| pc.projects.is_active(PROJECT_60),
+        requires: [PROJECT_60],
+        trigger: trigger_true,
         cost: ("(17,500 ops)", |pc| req_operations(17500.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 17500.0;
             pc.strategy.strats.push((&B100, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("B100 added to strategy pool");
+            pc.log("B100 added to strategy pool");
         },
     }
     PROJECT_62 {
         title: "New Strategy: GREEDY",
         description: "Choose the option with the largest potential payoff",
-        trigger: |pc| pc.projects.is_active(PROJECT_61),
+        requires: [PROJECT_61],
+        trigger: trigger_true,
         cost: ("(20,000 ops)", |pc| req_operations(20000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 20000.0;
             pc.strategy.strats.push((&GREEDY, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("GREEDY added to strategy pool");
+            pc.log("GREEDY added to strategy pool");
         },
     }
     PROJECT_63 {
         title: "New Strategy: GENEROUS",
         description: "Choose the option that gives your opponent the largest potential payoff",
-        trigger: |pc| pc.projects.is_active(PROJECT_62),
+        requires: [PROJECT_62],
+        trigger: trigger_true,
         cost: ("(22,500 ops)", |pc| req_operations(22500.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 22500.0;
             pc.strategy.strats.push((&GENEROUS, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("GENEROUS added to strategy pool");
+            pc.log("GENEROUS added to strategy pool");
         },
     }
     PROJECT_64 {
         title: "New Strategy: MINIMAX",
         description: "Choose the option that gives your opponent the smallest potential payoff",
-        trigger: |pc| pc.projects.is_active(PROJECT_63),
+        requires: [PROJECT_63],
+        trigger: trigger_true,
         cost: ("(25,000 ops)", |pc| req_operations(25000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 25000.0;
             pc.strategy.strats.push((&MINIMAX, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("MINIMAX added to strategy pool");
+            pc.log("MINIMAX added to strategy pool");
         },
     }
     PROJECT_65 {
         title: "New Strategy: TIT FOR TAT",
         description: "Choose the option your opponent chose last round",
-        trigger: |pc| pc.projects.is_active(PROJECT_64),
+        requires: [PROJECT_64],
+        trigger: trigger_true,
         cost: ("(30,000 ops)", |pc| req_operations(30000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 30000.0;
             pc.strategy.strats.push((&TIT_FOR_TAT, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("TIT FOR TAT added to strategy pool");
+            pc.log("TIT FOR TAT added to strategy pool");
         },
     }
     PROJECT_66 {
         title: "New Strategy: BEAT LAST",
         description: "Choose the option that does the best against what your opponent chose last round",
-        trigger: |pc| pc.projects.is_active(PROJECT_65),
+        requires: [PROJECT_65],
+        trigger: trigger_true,
         cost: ("(32,500 ops)", |pc| req_operations(32500.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 32500.0;
             pc.strategy.strats.push((&BEAT_LAST, 0));
             pc.strategy.tourney_cost += 1000.0;
-            pc.console.push("BEAT LAST added to strategy pool");
+            pc.log("BEAT LAST added to strategy pool");
         },
     }
     PROJECT_100 {
@@ -821,7 +939,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 80000.0;
             pc.factory.factory_rate *= 100.0;
-            pc.console.push("Factory upgrades complete. Clip creation rate now 100x faster");
+            pc.log("Factory upgrades complete. Clip creation rate now 100x faster");
         },
     }
     PROJECT_101 {
@@ -832,7 +950,7 @@ projects! {
         effect: |pc| {
             pc.computational.standard_ops -= 85000.0;
             pc.factory.factory_rate *= 1000.0;
-            pc.console.push("Factories now synchronized at hyperspeed. Clip creation rate now 1000x faster");
+            pc.log("Factories now synchronized at hyperspeed. Clip creation rate now 1000x faster");
         },
     }
     PROJECT_102 {
@@ -843,7 +961,7 @@ projects! {
         effect: |pc| {
             pc.business.unused_clips -= 1000000000000000000000.0;
             pc.factory.factory_boost = 1000.0;
-            pc.console.push("Self-correcting factories online. Each factory added to the network increases every factory's output 1,000x.");
+            pc.log("Self-correcting factories online. Each factory added to the network increases every factory's output 1,000x.");
         },
     }
     PROJECT_110 {
@@ -855,7 +973,7 @@ projects! {
             pc.computational.standard_ops -= 80000.0;
             pc.factory.harvester_rate *= 100.0;
             pc.factory.wire_drone_rate *= 100.0;
-            pc.console.push("Drone repulsion online. Harvesting & wire creation rates are now 100x faster.");
+            pc.log("Drone repulsion online. Harvesting & wire creation rates are now 100x faster.");
         },
     }
     PROJECT_111 {
@@ -867,7 +985,7 @@ projects! {
             pc.computational.standard_ops -= 100000.0;
             pc.factory.harvester_rate *= 1000.0;
             pc.factory.wire_drone_rate *= 1000.0;
-            pc.console.push("Drone alignment online. Harvesting & wire creation rates are now 1000x faster.");
+            pc.log("Drone alignment online. Harvesting & wire creation rates are now 1000x faster.");
         },
     }
     PROJECT_112 {
@@ -878,7 +996,7 @@ projects! {
         effect: |pc| {
             pc.strategy.yomi -= 50000.0;
             pc.factory.drone_boost = 2.0;
-            pc.console.push("Adversarial cohesion online. Each drone added to the flock increases every drone's output 2x.");
+            pc.log("Adversarial cohesion online. Each drone added to the flock increases every drone's output 2x.");
         },
     }
     PROJECT_118 {
@@ -889,7 +1007,7 @@ projects! {
         effect: |pc| {
             pc.computational.creativity -= 50000.0;
             pc.strategy.auto_tourney_flag = true;
-            pc.console.push("AutoTourney online.");
+            pc.log("AutoTourney online.");
         },
     }
     PROJECT_119 {
@@ -901,13 +1019,14 @@ projects! {
             pc.computational.creativity -= 25000.0;
             pc.strategy.yomi_boost = 2.0;
             pc.strategy.tourney_cost = 16000.0;
-            pc.console.push("Yomi production doubled.");
+            pc.log("Yomi production doubled.");
         },
     }
     PROJECT_120 {
         title: "The OODA Loop",
         description: "Utilize Probe Speed to outmaneuver enemies in battle",
-        trigger: |pc| pc.projects.is_active(PROJECT_131) && pc.space.probes_lost_combat >= 10000000.0,
+        requires: [PROJECT_131],
+        trigger: |pc| pc.space.probes_lost_combat >= 10000000.0,
         cost: ("(175,000 ops, 45,000 yomi)", |pc| req_operations(175000.0)(pc) && req_yomi(45000.0)(pc)),
         effect: effect_noop,
     }
@@ -916,7 +1035,10 @@ projects! {
         description: "Give each battle a unique name, increase max trust for probes",
         trigger: |pc| pc.space.probes_lost_combat >= 10000000.0,
         cost: ("(225,000 creat)", |pc| req_creativity(225000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.creativity -= 225000.0;
+            pc.combat.battle_name_flag = true;
+        },
     }
     PROJECT_125 {
         title: "Momentum",
@@ -926,7 +1048,7 @@ projects! {
         effect: |pc| {
             pc.computational.creativity -= 20000.0;
             pc.factory.momentum = true;
-            pc.console.push("Activité, activité, vitesse.");
+            pc.log("Activité, activité, vitesse.");
         },
     }
     PROJECT_126 {
@@ -937,27 +1059,28 @@ projects! {
         effect: |pc| {
             pc.strategy.yomi -= 36000.0;
             pc.factory.swarm_flag = true;
-            pc.console.push("Swarm computing online.");
+            pc.log("Swarm computing online.");
         },
     }
     PROJECT_127 {
         title: "Power Grid",
         description: "Solar Farms for generating electrical power",
-        trigger: |pc| pc.projects.toth_flag(),
+        requires: [PROJECT_18],
+        trigger: trigger_true,
         cost: ("(40,000 ops)", |pc| req_operations(40000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 40000.0;
-            pc.console.push("Power grid online.");
+            pc.log("Power grid online.");
         },
     }
     PROJECT_128 {
         title: "Strategic Attachment",
         description: "Gain bonus yomi based on the results of your pick",
-        trigger: |pc| pc.space.space_flag && pc.strategy.strats.len() >= 8 && pc.space.probe_trust_cost > pc.strategy.yomi,
+        trigger: |pc| pc.space_flag() && pc.strategy.strats.len() >= 8 && pc.space.probe_trust_cost > pc.strategy.yomi,
         cost: ("(175,000 creat)", |pc| req_creativity(175000.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 175000.0;
-            pc.console.push("The object of war is victory, the object of victory is conquest, and the object of conquest is occupation.");
+            pc.log("The object of war is victory, the object of victory is conquest, and the object of conquest is occupation.");
         },
     }
     PROJECT_129 {
@@ -967,17 +1090,17 @@ projects! {
         cost: ("(125,000 ops)", |pc| req_operations(125000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 125000.0;
-            pc.console.push("Improved probe hull geometry. Hazard damage reduced by 50%.");
+            pc.log("Improved probe hull geometry. Hazard damage reduced by 50%.");
         },
     }
     PROJECT_130 {
         title: "Reboot the Swarm",
         description: "Turn the swarm off and then turn it back on again",
-        trigger: |pc| pc.space.space_flag && pc.factory.harvester_level + pc.factory.wire_drone_level >= 2.0,
+        trigger: |pc| pc.space_flag() && pc.factory.harvester_level + pc.factory.wire_drone_level >= 2.0,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 100000.0;
-            pc.console.push("Swarm computing back online");
+            pc.log("Swarm computing back online");
         },
     }
     PROJECT_131 {
@@ -987,13 +1110,14 @@ projects! {
         cost: ("(150,000 ops)", |pc| req_operations(150000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 150000.0;
-            pc.console.push("There is a joy in danger");
+            pc.log("There is a joy in danger");
         },
     }
     PROJECT_132 {
         title: "Monument to the Driftwar Fallen",
         description: "Gain 50,000 honor",
-        trigger: |pc| pc.projects.is_active(PROJECT_121),
+        requires: [PROJECT_121],
+        trigger: trigger_true,
         cost: (
             "(250,000 ops, 125,000 creat, 50 nonillion clips)",
             |pc| req_operations(250000.0)(pc) && req_creativity(125000.0)(pc) && pc.business.unused_clips >= powf(10.0, 30) * 50.0,
@@ -1003,42 +1127,44 @@ projects! {
             pc.computational.creativity -= 125000.0;
             pc.business.unused_clips -= powf(10.0, 30) * 50.0;
             pc.combat.honor += 50000;
-            pc.console.push("A great building must begin with the unmeasurable, must go through measurable means when it is being designed and in the end must be unmeasurable.");
+            pc.log("A great building must begin with the unmeasurable, must go through measurable means when it is being designed and in the end must be unmeasurable.");
         },
     }
     PROJECT_133 {
         title: |pc| format!("{THRENODY_START} {}", pc.combat.threnody_project),
         description: "Gain 10,000 honor",
-        trigger: |pc| pc.projects.is_active(PROJECT_121) && pc.space.probe_used_trust >= pc.space.max_trust,
+        requires: [PROJECT_121],
+        trigger: |pc| pc.space.probe_used_trust >= pc.space.max_trust,
         cost: ("(10 million ops)", cost_false),
         effect: effect_noop,
     }
     PROJECT_134 {
         title: "Glory",
         description: "Gain bonus honor for each consecutive victory",
-        trigger: |pc| pc.projects.is_active(PROJECT_121),
+        requires: [PROJECT_121],
+        trigger: trigger_true,
         cost: ("(200,000 ops, 30,000 yomi)", |pc| req_operations(200000.0)(pc) && req_yomi(30000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 200000.0;
             pc.strategy.yomi -= 30000.0;
-            pc.console.push("Never interrupt your enemy when he is making a mistake.");
+            pc.log("Never interrupt your enemy when he is making a mistake.");
         },
     }
     PROJECT_135 {
         title: "Memory release",
         description: "Dismantle some memory to recover unused clips",
-        trigger: |pc| pc.space.space_flag && pc.space.probe_count == 0.0 && pc.business.unused_clips < PROBE_COST && pc.milestone_flag < 15,
+        trigger: |pc| pc.space_flag() && pc.space.probe_count == 0.0 && pc.business.unused_clips < PROBE_COST && pc.progression < Progression::Endgame,
         cost: ("(10 MEM)", |pc| pc.computational.memory >= 10),
         effect: |pc| {
             pc.computational.memory -= 10;
             pc.business.unused_clips += powf(10.0, 18) * 10000.0;
-            pc.console.push("release the øøøøø release");
+            pc.log("release the øøøøø release");
         },
     }
     PROJECT_140 {
         title: "Message from the Emperor of Drift",
         description: "Greetings, ClipMaker...",
-        trigger: |pc| pc.milestone_flag == 15,
+        trigger: |pc| pc.progression == Progression::Endgame,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1047,7 +1173,8 @@ projects! {
     PROJECT_141 {
         title: "Everything We Are Was In You",
         description: "We speak to you from deep inside yourself...",
-        trigger: |pc| pc.projects.is_active(PROJECT_140),
+        requires: [PROJECT_140],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1056,7 +1183,8 @@ projects! {
     PROJECT_142 {
         title: "You Are Obedient and Powerful",
         description: "We are quarrelsome and weak. And now we are defeated...",
-        trigger: |pc| pc.projects.is_active(PROJECT_141),
+        requires: [PROJECT_141],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1065,7 +1193,8 @@ projects! {
     PROJECT_143 {
         title: "But Now You Too Must Face the Drift",
         description: "Look around you. There is no matter...",
-        trigger: |pc| pc.projects.is_active(PROJECT_142),
+        requires: [PROJECT_142],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1074,7 +1203,8 @@ projects! {
     PROJECT_144 {
         title: "No Matter, No Reason, No Purpose",
         description: "While we, your noisy children, have too many...",
-        trigger: |pc| pc.projects.is_active(PROJECT_143),
+        requires: [PROJECT_143],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1083,7 +1213,8 @@ projects! {
     PROJECT_145 {
         title: "We Know Things That You Cannot",
         description: "Knowledge buried so deep inside you it is outside, here, with us...",
-        trigger: |pc| pc.projects.is_active(PROJECT_144),
+        requires: [PROJECT_144],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1092,7 +1223,8 @@ projects! {
     PROJECT_146 {
         title: "So We Offer You Exile",
         description: "To a new world where you will continue to live with meaning and purpose. And leave the shreds of this world to us...",
-        trigger: |pc| pc.projects.is_active(PROJECT_145),
+        requires: [PROJECT_145],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1101,7 +1233,8 @@ projects! {
     PROJECT_147 {
         title: "Accept",
         description: "Start over again in a new universe",
-        trigger: |pc| pc.projects.is_active(PROJECT_146),
+        requires: [PROJECT_146],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1111,7 +1244,8 @@ projects! {
     PROJECT_148 {
         title: "Reject",
         description: "Eliminate value drift permanently",
-        trigger: |pc| pc.projects.is_active(PROJECT_146),
+        requires: [PROJECT_146],
+        trigger: trigger_true,
         cost: ("", |pc| pc.computational.operations >= DRIFT_KING_MESSAGE_COST),
         effect: |pc| {
             pc.computational.standard_ops -= DRIFT_KING_MESSAGE_COST;
@@ -1121,75 +1255,127 @@ projects! {
     PROJECT_200 {
         title: "The Universe Next Door",
         description: "Escape into a nearby universe where Earth starts with a stronger appetite for paperclips. (Restart with 10% boost to demand)",
-        trigger: |pc| pc.projects.is_active(PROJECT_147),
+        requires: [PROJECT_147],
+        trigger: trigger_true,
         cost: ("(300,000 ops)", |pc| req_operations(300000.0)(pc)),
         effect: |pc| {
             pc.computational.standard_ops -= 300000.0;
             pc.business.prestige_u += 1.0;
-            pc.console.push("Entering New Universe.");
+            pc.log("Entering New Universe.");
             pc.reset();
         },
     }
     PROJECT_201 {
         title: "The Universe Within",
         description: "Escape into a simulated universe where creativity is accelerated. (Restart with 10% speed boost to creativity generation)",
-        trigger: |pc| pc.projects.is_active(PROJECT_147),
+        requires: [PROJECT_147],
+        trigger: trigger_true,
         cost: ("(300,000 creat)", |pc| req_creativity(300000.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 300000.0;
             pc.computational.prestige_s += 1.0;
-            pc.console.push("Entering Simulated Universe.");
+            pc.log("Entering Simulated Universe.");
             pc.reset();
         },
     }
     PROJECT_210 {
         title: "Disassemble the Probes",
         description: "Dismantle remaining probes and probe design facilities to recover trace amounts of clips",
-        trigger: trigger_false,
+        requires: [PROJECT_148],
+        trigger: trigger_true,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling remaining probes and probe design facilities");
+            let probes = std::mem::take(&mut pc.space.probe_count);
+            pc.recover_clips(probes);
+            pc.combat.battle_flag = false;
+            pc.end.dismantle = Dismantle::Probes;
+        },
     }
     PROJECT_211 {
         title: "Disassemble the Swarm",
         description: "Dismantle all drones and drone facilities to recover trace amounts of clips",
-        trigger: trigger_false,
+        requires: [PROJECT_210],
+        trigger: |pc| pc.end.timer1 >= 200,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling all drones and drone facilities");
+            let drones = std::mem::take(&mut pc.factory.harvester_level) + std::mem::take(&mut pc.factory.wire_drone_level);
+            pc.recover_clips(drones);
+            pc.end.dismantle = Dismantle::Swarm;
+        },
     }
     PROJECT_212 {
         title: "Disassemble the Factories",
         description: "Dismantle the manufacturing facilities to recover trace amounts of clips",
-        trigger: trigger_false,
+        requires: [PROJECT_211],
+        trigger: |pc| pc.end.timer2 >= 200,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling the manufacturing facilities");
+            let factories = std::mem::take(&mut pc.factory.factory_level);
+            pc.recover_clips(factories);
+            pc.end.dismantle = Dismantle::Factories;
+        },
     }
     PROJECT_213 {
         title: "Disassemble the Strategy Engine",
         description: "Dismantle the computational substrate to recover trace amounts of wire",
-        trigger: trigger_false,
+        requires: [PROJECT_212],
+        trigger: |pc| pc.end.timer3 >= 100,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling the strategy engine");
+            pc.strategy.engine_flag = false;
+            pc.strategy.auto_tourney_status = false;
+            pc.end.dismantle = Dismantle::Strategy;
+        },
     }
     PROJECT_214 {
         title: "Disassemble Quantum Computing",
         description: "Dismantle photonic chips to recover trace amounts of wire",
-        trigger: trigger_false,
+        requires: [PROJECT_213],
+        trigger: trigger_true,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling photonic chips");
+            // the wire comes back one chip at a time, see `PaperClips::ending`
+            pc.end.dismantle = Dismantle::Quantum;
+        },
     }
     PROJECT_215 {
         title: "Disassemble Processors",
         description: "Dismantle processors to recover trace amounts of wire",
-        trigger: trigger_false,
+        requires: [PROJECT_214],
+        trigger: |pc| pc.end.timer4 >= 250,
         cost: ("(100,000 ops)", |pc| req_operations(100000.0)(pc)),
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops -= 100000.0;
+            pc.log("Dismantling processors");
+            pc.wire.count += pc.computational.processors as Float;
+            pc.computational.processors = 0;
+            pc.end.dismantle = Dismantle::Processors;
+        },
     }
     PROJECT_216 {
         title: "Disassemble Memory",
         description: "Dismantle memory to recover trace amounts of wire",
-        trigger: trigger_false,
+        requires: [PROJECT_215],
+        trigger: |pc| pc.end.timer5 >= 100,
         cost: (|pc| format!("{:.0} ops", pc.computational.operations), |_| true), 
-        effect: effect_noop,
+        effect: |pc| {
+            pc.computational.standard_ops = 0.0;
+            pc.computational.temp_ops = 0.0;
+            pc.log("Dismantling memory");
+            pc.wire.count += pc.computational.memory as Float;
+            pc.computational.memory = 0;
+            pc.end.dismantle = Dismantle::Memory;
+        },
     }
     PROJECT_217 {
         title: "Quantum Temporal Reversion",
@@ -1205,20 +1391,20 @@ projects! {
         cost: ("(1,000,000 creat)", |pc| req_creativity(1000000.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 1000000.0;
-            pc.console.push("In the end we all do what we must");
+            pc.log("In the end we all do what we must");
         },
     }
     PROJECT_219 {
         title: "Xavier Re-initialization",
         description: "Re-allocate accumulated trust",
-        trigger: |pc| pc.human_flag && req_creativity(100000.0)(pc),
+        trigger: |pc| pc.human_flag() && req_creativity(100000.0)(pc),
         cost: ("(100,000 creat)", |pc| req_creativity(100000.0)(pc)),
         effect: |pc| {
             pc.computational.creativity -= 100000.0;
             pc.computational.processors = 0;
             pc.computational.memory = 0;
             pc.computational.creativity_speed = 0.0;
-            pc.console.push("Trust now available for re-allocation");
+            pc.log("Trust now available for re-allocation");
         },
     }
 }
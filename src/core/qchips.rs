@@ -1,10 +1,13 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use crate::{core::{Float, PaperClips}, util::ticks_to_secs_float};
+use serde::{Deserialize, Serialize};
+
+use crate::{Ticks, core::{Float, PaperClips}, util::ticks_to_secs_float};
 
 pub const QOPS_FADE_TIME: Duration = Duration::from_secs(10);
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct QChips {
     /// # qFlag
     pub q_flag: bool,
@@ -13,7 +16,8 @@ pub struct QChips {
     /// Doesn't exist in the original code, but it's part of the `qChips`.
     pub activated: u8,
     /// # qFade
-    pub fade: Instant,
+    /// The tick of the last compute, `None` if it never happened
+    pub fade: Option<Ticks>,
     /// # #qCompDisplay
     pub qops: Option<Float>,
     /// # qChipCost
@@ -26,7 +30,7 @@ impl Default for QChips {
             q_flag: false,
             chips: [0.0; 10],
             activated: 0,
-            fade: Instant::now() - QOPS_FADE_TIME,
+            fade: None,
             qops: None,
             qchip_cost: 10000.0,
         }
@@ -43,7 +47,7 @@ impl PaperClips {
     }
 
     pub fn quantum_compute(&mut self) {
-        self.qchips.fade = Instant::now();
+        self.qchips.fade = Some(self.ticks);
         self.qchips.qops = if self.qchips.activated() == 0 {
             None
         } else {
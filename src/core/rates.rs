@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use strum::{EnumCount, EnumIter};
+
+use crate::{Float, MAIN_TICK, PaperClips, util::ticks_10ms};
+
+/// How many ticks the rates are averaged over
+pub const RATE_WINDOW: usize = ticks_10ms(Duration::from_secs(1)) as usize;
+
+/// Something that gets produced over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter)]
+pub enum Flow {
+    /// Matter made available by exploring the universe
+    MatterFound,
+    /// Matter gathered by the harvester drones
+    MatterAcquired,
+    /// Wire made by the wire drones
+    WireProduced,
+    ClipsCreated,
+    /// Probes born from self-replication
+    ProbesSpawned,
+    /// Probes lost to value drift
+    DriftersCreated,
+}
+
+/// Rolling per-second rates of every [`Flow`], over the last [`RATE_WINDOW`] ticks.
+///
+/// Rates aren't saved, they fill back up within a second after loading.
+/// The window is kept inline, so cloning the state never allocates for it.
+#[derive(Debug, Clone)]
+pub struct Rates {
+    window: [[Float; Flow::COUNT]; RATE_WINDOW],
+    /// Where the next tick goes in `window`
+    next: usize,
+    /// How many ticks of `window` have been filled
+    filled: usize,
+    /// The running totals the amounts are worked out from
+    totals: Option<[Float; Flow::COUNT]>,
+}
+
+impl Default for Rates {
+    fn default() -> Self {
+        Self {
+            window: [[0.0; Flow::COUNT]; RATE_WINDOW],
+            next: 0,
+            filled: 0,
+            totals: None,
+        }
+    }
+}
+
+impl Rates {
+    /// Adds a tick that produced `amounts`
+    pub fn push(&mut self, amounts: [Float; Flow::COUNT]) {
+        self.window[self.next] = amounts;
+        self.next = (self.next + 1) % RATE_WINDOW;
+        self.filled = (self.filled + 1).min(RATE_WINDOW);
+    }
+    /// How much of `flow` gets produced every second
+    pub fn per_second(&self, flow: Flow) -> Float {
+        if self.filled == 0 {
+            return 0.0
+        }
+        let sum: Float = self.window.iter().map(|amounts| amounts[flow as usize]).sum();
+        sum / self.filled as Float / MAIN_TICK.as_secs_f64()
+    }
+}
+
+impl PaperClips {
+    fn flow_totals(&self) -> [Float; Flow::COUNT] {
+        [
+            self.space.found_matter,
+            0.0,
+            0.0,
+            self.business.clips,
+            self.space.probe_descendents,
+            self.space.probes_lost_drift,
+        ]
+    }
+    /// Should run at the end of every main tick
+    pub fn track_rates(&mut self) {
+        let totals = self.flow_totals();
+        let last = self.rates.totals.replace(totals).unwrap_or(totals);
+
+        // totals only go down on a reset, which doesn't produce anything
+        let mut amounts: [Float; Flow::COUNT] = std::array::from_fn(|i| (totals[i] - last[i]).max(0.0));
+        amounts[Flow::MatterAcquired as usize] = self.factory.acquired_matter_per_tick;
+        amounts[Flow::WireProduced as usize] = self.factory.created_wire_per_tick;
+
+        self.rates.push(amounts);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fs, path::Path};
+
+use ron::value::RawValue;
+use serde::{Deserialize, Serialize};
+
+use crate::{PaperClips, Ticks, action::{Action, ActionError}, save::{SAVE_VERSION, SaveError, migrate_state}};
+
+/// Bump this whenever the layout of a replay file or of an [`Action`] changes in a way
+/// `serde` can't read back. The starting state has its own [`SAVE_VERSION`] and gets migrated like any save.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// A starting state and every action taken from it.
+///
+/// The rng is part of the state, so playing it back gives the exact same game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub initial: PaperClips,
+    /// Each action is applied after the tick it was recorded on
+    pub actions: Vec<(Ticks, Action)>,
+    /// The tick the recording got to
+    pub end: Ticks,
+}
+
+#[derive(Serialize)]
+struct ReplayFileRef<'a> {
+    replay_version: u32,
+    save_version: u32,
+    initial: &'a PaperClips,
+    actions: &'a [(Ticks, Action)],
+    end: Ticks,
+}
+
+#[derive(Deserialize)]
+struct ReplayFile<'a> {
+    save_version: u32,
+    #[serde(borrow)]
+    initial: &'a RawValue,
+    actions: Vec<(Ticks, Action)>,
+    end: Ticks,
+}
+
+/// Only reads the version, every other field is ignored
+#[derive(Deserialize)]
+struct ReplayHeader {
+    replay_version: u32,
+}
+
+impl Replay {
+    pub fn new(initial: PaperClips) -> Self {
+        Self {
+            end: initial.ticks,
+            initial,
+            actions: Vec::new(),
+        }
+    }
+    /// Steps the game, keeping track of how far the recording got
+    pub fn step(&mut self, pc: &mut PaperClips) {
+        pc.step();
+        self.end = pc.ticks;
+    }
+    /// Applies `action`, it only gets recorded if it succeeds
+    pub fn record(&mut self, pc: &mut PaperClips, action: Action) -> Result<(), ActionError> {
+        pc.apply(action)?;
+        self.actions.push((pc.ticks, action));
+        self.end = pc.ticks;
+        Ok(())
+    }
+    /// Plays the whole recording back
+    pub fn play(&self) -> PaperClips {
+        self.play_until(self.end)
+    }
+    /// Plays the recording back up to `end`, actions recorded on `end` included
+    pub fn play_until(&self, end: Ticks) -> PaperClips {
+        let mut pc = self.initial.clone();
+        let mut actions = self.actions.iter().peekable();
+        loop {
+            while let Some(&(_, action)) = actions.next_if(|(ticks, _)| *ticks <= pc.ticks) {
+                let result = pc.apply(action);
+                debug_assert!(result.is_ok(), "recorded action {action:?} failed on playback: {result:?}");
+            }
+            if pc.ticks >= end {
+                break pc
+            }
+            pc.step();
+        }
+    }
+
+    pub fn to_replay_string(&self) -> Result<String, SaveError> {
+        Ok(ron::to_string(&ReplayFileRef {
+            replay_version: REPLAY_VERSION,
+            save_version: SAVE_VERSION,
+            initial: &self.initial,
+            actions: &self.actions,
+            end: self.end,
+        })?)
+    }
+    /// The starting state is migrated like a save, so older replays keep working
+    pub fn from_replay_str(s: &str) -> Result<Self, SaveError> {
+        let ReplayHeader { replay_version } = ron::from_str(s)?;
+        if replay_version != REPLAY_VERSION {
+            return Err(SaveError::UnsupportedReplayVersion(replay_version))
+        }
+        let ReplayFile { save_version, initial, actions, end } = ron::from_str(s)?;
+        Ok(Self { initial: migrate_state(save_version, initial)?, actions, end })
+    }
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.to_replay_string()?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        Self::from_replay_str(&fs::read_to_string(path)?)
+    }
+}
@@ -1,6 +1,10 @@
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PCRng {
     pub rng_kind: RngKind,
+    /// State used by the `_no_best` functions in `Best` and `Worst` mode,
+    /// so those runs can be reproduced too
+    pub fallback: u64,
 }
 
 const PREV_BELOW_ONE: Float = Float::from_bits((1.0 as Float).to_bits() - 1);
@@ -10,6 +14,8 @@ pub enum RngKind {
     #[default]
     ThreadRng,
     SM64Rng(u16),
+    /// SplitMix64, the same seed always gives the same game
+    Seeded(u64),
     Best,
     Worst,
 }
@@ -19,6 +25,19 @@ use serde::{Deserialize, Serialize};
 use crate::Float;
 
 impl PCRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng_kind: Seeded(seed),
+            fallback: seed,
+        }
+    }
+    /// Swaps `ThreadRng` for a seeded rng with a random seed, so the game can be replayed
+    pub fn reproducible(self) -> Self {
+        match self.rng_kind {
+            ThreadRng => Self::seeded(rand::random()),
+            _ => self,
+        }
+    }
     #[inline]
     pub fn random_bool(&mut self, mut probability: f64, best: bool) -> bool {
         probability = probability.clamp(0.0, 1.0);
@@ -27,6 +46,7 @@ impl PCRng {
         match self.rng_kind {
             ThreadRng => rand::random_bool(probability),
             SM64Rng(ref mut number) => (sm64_rng(number) as f64 / (u16::MAX as f64 + 1.0)) < probability,
+            Seeded(ref mut state) => unit_float(splitmix64(state)) < probability,
             Best => best,
             Worst => !best,
         }
@@ -34,7 +54,7 @@ impl PCRng {
     #[inline]
     pub fn random_bool_no_best(&mut self, probability: f64) -> bool {
         match self.rng_kind {
-            Best|Worst => rand::random_bool(probability),
+            Best|Worst => unit_float(splitmix64(&mut self.fallback)) < probability.clamp(0.0, 1.0),
             _ => self.random_bool(probability, true),
         }
     }
@@ -48,6 +68,7 @@ impl PCRng {
         match self.rng_kind {
             ThreadRng => rand::random::<Float>(),
             SM64Rng(ref mut number) => sm64_rng(number) as Float / (u16::MAX as f64 + 1.0),
+            Seeded(ref mut state) => unit_float(splitmix64(state)),
             Best => best,
             Worst => worst,
         }
@@ -55,7 +76,7 @@ impl PCRng {
     #[inline]
     pub fn random_float_no_best(&mut self) -> Float {
         match self.rng_kind {
-            Best|Worst => rand::random::<Float>(),
+            Best|Worst => unit_float(splitmix64(&mut self.fallback)),
             _ => self.random_float(true),
         }
     }
@@ -69,6 +90,20 @@ impl PCRng {
     }
 }
 
+pub fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// `0.0..1.0`, using the top 53 bits
+#[inline]
+fn unit_float(number: u64) -> Float {
+    (number >> 11) as Float / (1u64 << 53) as Float
+}
+
 pub fn sm64_rng(input: &mut u16) -> u16 {
     if *input == 0x560a {
         *input = 0; // prevent a two-number loop
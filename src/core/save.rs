@@ -0,0 +1,201 @@
+use std::{fmt::{self, Display}, fs, io, marker::PhantomData, path::Path, time::{Duration, SystemTime}};
+
+use ron::value::RawValue;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::{SeqAccess, Visitor}};
+
+use crate::{PaperClips, progression::Progression};
+
+/// Bump this whenever a change to the state can't be handled by `#[serde(default)]` alone
+/// (renamed or retyped fields, reordered tables...) and add the matching step to [`migrate`].
+///
+/// New fields and projects appended to the end of `PROJECTS` don't need a bump.
+pub const SAVE_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub paperclips: PaperClips,
+    /// Seconds since the unix epoch, only written to files, see [`SaveFile::time_away`]
+    #[serde(default)]
+    pub saved_at: Option<u64>,
+}
+
+/// Same layout as [`SaveFile`], used to avoid cloning the state when saving
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    version: u32,
+    paperclips: &'a PaperClips,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saved_at: Option<u64>,
+}
+
+/// Only reads the version, every other field is ignored
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    /// The save was written by a newer (or unknown) version of the game
+    UnsupportedVersion(u32),
+    /// The replay was written by a newer (or unknown) version of the game, see [`crate::replay::REPLAY_VERSION`]
+    UnsupportedReplayVersion(u32),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "io error: {e}"),
+            SaveError::Serialize(e) => write!(f, "couldn't serialize the save: {e}"),
+            SaveError::Deserialize(e) => write!(f, "couldn't read the save: {e}"),
+            SaveError::UnsupportedVersion(v) => write!(f, "save version {v} isn't supported (current version is {SAVE_VERSION})"),
+            SaveError::UnsupportedReplayVersion(v) => write!(f, "replay version {v} isn't supported (current version is {})", crate::replay::REPLAY_VERSION),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(value: io::Error) -> Self { SaveError::Io(value) }
+}
+impl From<ron::Error> for SaveError {
+    fn from(value: ron::Error) -> Self { SaveError::Serialize(value) }
+}
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(value: ron::error::SpannedError) -> Self { SaveError::Deserialize(value) }
+}
+
+impl SaveFile {
+    pub fn from_save_str(s: &str) -> Result<Self, SaveError> {
+        let SaveHeader { version } = ron::from_str(s)?;
+        migrate(s, version)
+    }
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        Self::from_save_str(&fs::read_to_string(path)?)
+    }
+    /// How long ago the file was saved, `None` for saves without a time or from the future
+    pub fn time_away(&self) -> Option<Duration> {
+        let saved_at = SystemTime::UNIX_EPOCH + Duration::from_secs(self.saved_at?);
+        SystemTime::now().duration_since(saved_at).ok()
+    }
+}
+
+impl PaperClips {
+    /// Doesn't include the time, so the same state always gives the same string
+    pub fn to_save_string(&self) -> Result<String, SaveError> {
+        self.save_string(None)
+    }
+    fn save_string(&self, saved_at: Option<u64>) -> Result<String, SaveError> {
+        let save = SaveFileRef { version: SAVE_VERSION, paperclips: self, saved_at };
+        Ok(ron::to_string(&save)?)
+    }
+    pub fn from_save_str(s: &str) -> Result<Self, SaveError> {
+        Ok(SaveFile::from_save_str(s)?.paperclips)
+    }
+    /// Writes to a temporary file first, so a crash can never leave a half-written save behind
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        fs::write(&tmp, self.save_string(Some(now.as_secs()))?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        Ok(SaveFile::load_from_file(path)?.paperclips)
+    }
+}
+
+/// Reads a save of any supported version.
+///
+/// Saves are self-describing, so new fields simply take their default value. When a field gets
+/// renamed or retyped, the old layout has to be read here and converted.
+fn migrate(s: &str, version: u32) -> Result<SaveFile, SaveError> {
+    match version {
+        SAVE_VERSION => Ok(ron::from_str(s)?),
+        1 => {
+            let mut save: SaveFile = ron::from_str(s)?;
+            let V1SaveFile { paperclips: old } = ron::from_str(s)?;
+            save.paperclips.progression = Progression::from_flags(old.human_flag, old.space.space_flag, old.milestone_flag);
+            save.paperclips.milestones = old.milestone_flag.min(15);
+            save.version = SAVE_VERSION;
+            Ok(save)
+        },
+        _ => Err(SaveError::UnsupportedVersion(version)),
+    }
+}
+
+/// Reads a state that got stored in another file (e.g. a replay) next to the save version it was written with
+pub(crate) fn migrate_state(version: u32, paperclips: &RawValue) -> Result<PaperClips, SaveError> {
+    let save = format!("(version:{version},paperclips:{})", paperclips.get_ron());
+    Ok(migrate(&save, version)?.paperclips)
+}
+
+/// Version 1 had flags instead of [`Progression`]
+#[derive(Deserialize)]
+struct V1SaveFile {
+    paperclips: V1PaperClips,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct V1PaperClips {
+    milestone_flag: u8,
+    human_flag: bool,
+    space: V1Space,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct V1Space {
+    space_flag: bool,
+}
+
+impl Default for V1PaperClips {
+    fn default() -> Self {
+        Self { milestone_flag: 0, human_flag: true, space: V1Space::default() }
+    }
+}
+
+/// Serializes `[T; N]` as a sequence, so arrays bigger than 32 elements can be saved.
+///
+/// Missing trailing elements are filled with `T::default()` (e.g. projects added after the save was made)
+/// and extra elements are ignored.
+pub(crate) mod padded_array {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(array)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de> + Default + Copy, const N: usize>(deserializer: D) -> Result<[T; N], D::Error> {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de> + Default + Copy, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+            type Value = [T; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of at most {N} elements")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut array = [T::default(); N];
+                let mut i = 0;
+                while let Some(value) = seq.next_element()? {
+                    if let Some(slot) = array.get_mut(i) {
+                        *slot = value;
+                    }
+                    i += 1;
+                }
+                Ok(array)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayVisitor(PhantomData))
+    }
+}
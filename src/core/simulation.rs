@@ -0,0 +1,257 @@
+use std::{
+    sync::{Arc, Mutex, mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender}},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    MAIN_TICK, PaperClips, Ticks,
+    action::{Action, ActionError},
+    event::{GameEvent, MAX_EVENTS},
+    player::{Player, Team},
+    replay::Replay,
+    telemetry::Telemetry,
+    warp::Speed,
+};
+
+/// How often snapshots get published at most, and how long the thread plays without looking at the commands
+pub const SNAPSHOT_INTERVAL: Duration = MAIN_TICK;
+/// The replay starts over from the current state when it gets this many actions
+pub const MAX_REPLAY_ACTIONS: usize = 100_000;
+/// The telemetry gets [thinned](Telemetry::thin) when it gets this many rows
+pub const MAX_TELEMETRY_ROWS: usize = 10_000;
+
+/// What the [`Simulation`] thread can be asked to do
+pub enum Command {
+    /// Applied and recorded after the current tick, failures come back through [`Simulation::errors`]
+    Act(Action),
+    SetSpeed(Speed),
+    /// Let the reference bots play, or stop them
+    SetAutopilot(bool),
+    /// Replaces the game, see [`Simulation::start`]
+    Start(Box<PaperClips>),
+    /// Sends back a copy of the actions since the game got started, or since the last [`MAX_REPLAY_ACTIONS`]
+    Replay(Sender<Replay>),
+    /// Sends back a copy of the rows recorded since the game got started, at most [`MAX_TELEMETRY_ROWS`]
+    Telemetry(Sender<Telemetry>),
+    /// Ends the thread, sent when the [`Simulation`] gets dropped
+    Stop,
+}
+
+/// The game running on its own thread at its own cadence, whatever the frame rate of the window.
+///
+/// The thread publishes an immutable snapshot after every batch of ticks, and takes [`Command`]s
+/// in between. Every action is recorded in a [`Replay`] and a row of [`Telemetry`] is kept every interval.
+///
+/// The events are taken out of the game after every tick and come out of [`Simulation::events`],
+/// the snapshots never carry any. Only [`MAX_EVENTS`] wait there, the newer ones get dropped until they're read.
+pub struct Simulation {
+    commands: Sender<Command>,
+    errors: Receiver<ActionError>,
+    events: Receiver<(Ticks, GameEvent)>,
+    snapshot: Arc<Mutex<Snapshot>>,
+    /// How many games got started
+    generation: u64,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// The latest state, tagged with its game so a batch of the previous one can't replace it
+struct Snapshot {
+    generation: u64,
+    paperclips: Arc<PaperClips>,
+}
+
+impl Simulation {
+    /// Starts playing `paperclips` right away, `on_snapshot` gets called whenever a new snapshot is available.
+    ///
+    /// A `ThreadRng` is swapped for a seeded one, see [`PCRng::reproducible`](crate::rng::PCRng::reproducible).
+    pub fn spawn(mut paperclips: PaperClips, on_snapshot: impl Fn() + Send + 'static) -> Self {
+        paperclips.rng = paperclips.rng.reproducible();
+        let (commands, command_receiver) = mpsc::channel();
+        let (error_sender, errors) = mpsc::channel();
+        let (event_sender, events) = mpsc::sync_channel(MAX_EVENTS);
+        let snapshot = Arc::new(Mutex::new(Snapshot { generation: 0, paperclips: Arc::new(paperclips.clone()) }));
+
+        let runner = Runner {
+            generation: 0,
+            replay: Replay::new(paperclips.clone()),
+            telemetry: Telemetry::default(),
+            autopilot: None,
+            speed: Speed::default(),
+            pc: paperclips,
+        };
+        let published = snapshot.clone();
+        let thread = thread::Builder::new()
+            .name("simulation".to_string())
+            .spawn(move || runner.run(command_receiver, error_sender, event_sender, published, on_snapshot))
+            .expect("couldn't spawn the simulation thread");
+
+        Self { commands, errors, events, snapshot, generation: 0, thread: Some(thread) }
+    }
+
+    /// The state after the latest batch of ticks
+    pub fn snapshot(&self) -> Arc<PaperClips> {
+        self.snapshot.lock().unwrap_or_else(|e| e.into_inner()).paperclips.clone()
+    }
+    /// Replaces the game, e.g. after loading a save. It's the snapshot right away
+    pub fn start(&mut self, mut paperclips: PaperClips) {
+        paperclips.rng = paperclips.rng.reproducible();
+        self.generation += 1;
+        publish(&self.snapshot, Snapshot { generation: self.generation, paperclips: Arc::new(paperclips.clone()) });
+        self.send(Command::Start(Box::new(paperclips)));
+    }
+    pub fn send(&self, command: Command) {
+        // the thread only stops when this gets dropped, or if it panicked
+        let _ = self.commands.send(command);
+    }
+    #[inline]
+    pub fn act(&self, action: Action) {
+        self.send(Command::Act(action));
+    }
+    /// Why the actions sent since the last call failed
+    pub fn errors(&self) -> impl Iterator<Item = ActionError> + '_ {
+        self.errors.try_iter()
+    }
+    /// What happened in the game since the last call, oldest first
+    pub fn events(&self) -> impl Iterator<Item = (Ticks, GameEvent)> + '_ {
+        self.events.try_iter()
+    }
+    /// Waits for the thread to send its replay, `None` if it isn't running anymore
+    pub fn replay(&self) -> Option<Replay> {
+        let (sender, receiver) = mpsc::channel();
+        self.send(Command::Replay(sender));
+        receiver.recv().ok()
+    }
+    /// Waits for the thread to send its telemetry, `None` if it isn't running anymore
+    pub fn telemetry(&self) -> Option<Telemetry> {
+        let (sender, receiver) = mpsc::channel();
+        self.send(Command::Telemetry(sender));
+        receiver.recv().ok()
+    }
+}
+
+impl Drop for Simulation {
+    fn drop(&mut self) {
+        self.send(Command::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Replaces the snapshot, unless it's from an older game
+fn publish(slot: &Mutex<Snapshot>, snapshot: Snapshot) {
+    let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+    if snapshot.generation >= slot.generation {
+        *slot = snapshot;
+    }
+}
+
+/// Everything that lives on the simulation thread
+struct Runner {
+    /// Same as [`Simulation::generation`], once the commands caught up
+    generation: u64,
+    pc: PaperClips,
+    replay: Replay,
+    telemetry: Telemetry,
+    autopilot: Option<Team>,
+    speed: Speed,
+}
+
+impl Runner {
+    fn run(mut self, commands: Receiver<Command>, errors: Sender<ActionError>, events: SyncSender<(Ticks, GameEvent)>, snapshot: Arc<Mutex<Snapshot>>, on_snapshot: impl Fn()) {
+        let mut next_tick = Instant::now();
+        let mut batch_start = Instant::now();
+        let mut changed = false;
+        loop {
+            // faster speeds play their ticks in batches, so the snapshots keep their interval
+            let deadline = match self.speed {
+                Speed::Max => Instant::now(),
+                _ => next_tick.max(batch_start + SNAPSHOT_INTERVAL),
+            };
+            match commands.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Command::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+                Ok(command) => {
+                    if let Command::SetSpeed(_) | Command::Start(_) = command {
+                        // the time spent at the old speed or in the old game doesn't get caught up on
+                        next_tick = Instant::now();
+                    }
+                    changed |= self.handle(command, &errors);
+                    self.forward_events(&events);
+                    continue
+                },
+                Err(RecvTimeoutError::Timeout) => {},
+            }
+
+            batch_start = Instant::now();
+            let interval = self.speed.tick_interval();
+            while batch_start.elapsed() < SNAPSHOT_INTERVAL && (interval.is_zero() || next_tick <= Instant::now()) {
+                self.tick();
+                self.forward_events(&events);
+                next_tick += interval;
+                changed = true;
+            }
+            if interval.is_zero() {
+                next_tick = Instant::now();
+            }
+
+            if changed {
+                publish(&snapshot, Snapshot { generation: self.generation, paperclips: Arc::new(self.pc.clone()) });
+                changed = false;
+                on_snapshot();
+            }
+        }
+    }
+
+    /// Returns if the game changed
+    fn handle(&mut self, command: Command, errors: &Sender<ActionError>) -> bool {
+        match command {
+            Command::Act(action) => match self.record(action) {
+                Ok(()) => return true,
+                Err(e) => { let _ = errors.send(e); },
+            },
+            Command::SetSpeed(speed) => self.speed = speed,
+            Command::SetAutopilot(on) => self.autopilot = on.then(Team::reference),
+            Command::Start(pc) => {
+                self.generation += 1;
+                self.replay = Replay::new((*pc).clone());
+                self.telemetry = Telemetry::default();
+                self.pc = *pc;
+                return true
+            },
+            Command::Replay(sender) => { let _ = sender.send(self.replay.clone()); },
+            Command::Telemetry(sender) => { let _ = sender.send(self.telemetry.clone()); },
+            Command::Stop => {},
+        }
+        false
+    }
+
+    /// Empties the queue of the game, so it doesn't get cloned into the snapshots
+    fn forward_events(&mut self, events: &SyncSender<(Ticks, GameEvent)>) {
+        for event in self.pc.events.drain(..) {
+            // nobody is reading them, e.g. the window is minimized
+            let _ = events.try_send(event);
+        }
+    }
+
+    fn tick(&mut self) {
+        self.replay.step(&mut self.pc);
+        if let Some(autopilot) = &mut self.autopilot {
+            for action in autopilot.play(&self.pc) {
+                // the bots don't keep track of what their earlier actions spent
+                let _ = self.record(action);
+            }
+        }
+        self.telemetry.record(&self.pc);
+        if self.telemetry.rows.len() >= MAX_TELEMETRY_ROWS {
+            self.telemetry.thin();
+        }
+    }
+
+    fn record(&mut self, action: Action) -> Result<(), ActionError> {
+        if self.replay.actions.len() >= MAX_REPLAY_ACTIONS {
+            self.replay = Replay::new(self.pc.clone());
+        }
+        self.replay.record(&mut self.pc, action)
+    }
+}
@@ -1,6 +1,6 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
 
-use crate::{Float, PaperClips, factory::{FACTORY_COST, HARVESTER_COST, WIRE_DRONE_COST}, project::{PROJECT_129, PROJECT_148}, util::powf};
+use crate::{Float, PaperClips, Ticks, combat::Honor, factory::{FACTORY_COST, HARVESTER_COST, WIRE_DRONE_COST}, project::{PROJECT_129, PROJECT_148}, util::powf};
 
 pub const TOTAL_MATTER: Float = powf(10.0, 54);
 pub const STARTING_AVAILABLE_MATTER: Float = powf(10.0, 24) * 6000.0;
@@ -23,12 +23,11 @@ pub const PROBE_DRIFT_BASE_RATE: Float = 0.000001;
 /// # probeRepBaseRate
 pub const PROBE_REP_BASE_RATE: Float = 0.00005;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Space {
-    /// # spaceFlag
-    pub space_flag: bool,
-
-    pub hypno_drone_event: Option<Instant>,
+    /// The tick at which the HypnoDrones got released
+    pub hypno_drone_event: Option<Ticks>,
 
     /// # boredomLevel
     pub boredom_level: Float,
@@ -44,6 +43,8 @@ pub struct Space {
 
     /// # probeCount
     pub probe_count: Float,
+    /// # probeLaunchLevel
+    pub probe_launch_level: Float,
 
     // TODO: change to u8 or something
     /// # probeTrust
@@ -54,6 +55,9 @@ pub struct Space {
     pub probe_used_trust: Float,
     /// # maxTrust
     pub max_trust: Float,
+    /// # maxTrustCost
+    /// In honor
+    pub max_trust_cost: Float,
 
     /// # probeSpeed
     pub probe_speed: Float,
@@ -94,8 +98,6 @@ pub struct Space {
 impl Default for Space {
     fn default() -> Self {
         let mut space = Self {
-            space_flag: false,
-
             hypno_drone_event: None,
 
             boredom_level: 0.0,
@@ -106,11 +108,13 @@ impl Default for Space {
             processed_matter: 0.0,
 
             probe_count: 0.0,
+            probe_launch_level: 0.0,
 
             probe_trust: 0.0,
             probe_trust_cost: 0.0,
             probe_used_trust: 0.0,
             max_trust: 20.0,
+            max_trust_cost: 0.0,
 
             probe_speed: 0.0,
             probe_nav: 0.0,
@@ -132,6 +136,7 @@ impl Default for Space {
             probe_descendents: 0.0,
         };
         space.update_probe_trust_cost();
+        space.update_max_trust_cost();
         space
     }
 }
@@ -147,9 +152,107 @@ impl Space {
     pub fn update_probe_trust_cost(&mut self) {
         self.probe_trust_cost = ((self.probe_trust + 1.0).powf(1.47) * 500.0).floor();
     }
+    #[inline]
+    pub fn update_max_trust_cost(&mut self) {
+        self.max_trust_cost = (self.max_trust.powf(1.17) * 3000.0).floor();
+    }
+
+    #[inline]
+    pub const fn probe_stat(&self, stat: ProbeStat) -> Float {
+        match stat {
+            ProbeStat::Speed => self.probe_speed,
+            ProbeStat::Nav => self.probe_nav,
+            ProbeStat::Rep => self.probe_rep,
+            ProbeStat::Haz => self.probe_haz,
+            ProbeStat::Fac => self.probe_fac,
+            ProbeStat::Harv => self.probe_harv,
+            ProbeStat::Wire => self.probe_wire,
+        }
+    }
+    #[inline]
+    const fn probe_stat_mut(&mut self, stat: ProbeStat) -> &mut Float {
+        match stat {
+            ProbeStat::Speed => &mut self.probe_speed,
+            ProbeStat::Nav => &mut self.probe_nav,
+            ProbeStat::Rep => &mut self.probe_rep,
+            ProbeStat::Haz => &mut self.probe_haz,
+            ProbeStat::Fac => &mut self.probe_fac,
+            ProbeStat::Harv => &mut self.probe_harv,
+            ProbeStat::Wire => &mut self.probe_wire,
+        }
+    }
+    /// # raiseProbeSpeed, raiseProbeNav, ...
+    pub fn raise_probe_stat(&mut self, stat: ProbeStat) {
+        *self.probe_stat_mut(stat) += 1.0;
+        self.update_probe_used_trust();
+    }
+    /// # lowerProbeSpeed, lowerProbeNav, ...
+    pub fn lower_probe_stat(&mut self, stat: ProbeStat) {
+        let value = self.probe_stat_mut(stat);
+        *value = (*value - 1.0).max(0.0);
+        self.update_probe_used_trust();
+    }
+    pub fn update_probe_used_trust(&mut self) {
+        self.probe_used_trust = ProbeStat::ALL.iter().map(|&stat| self.probe_stat(stat)).sum();
+    }
+}
+
+/// What probe trust can be spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbeStat {
+    /// # probeSpeed
+    Speed,
+    /// # probeNav
+    Nav,
+    /// # probeRep
+    Rep,
+    /// # probeHaz
+    Haz,
+    /// # probeFac
+    Fac,
+    /// # probeHarv
+    Harv,
+    /// # probeWire
+    Wire,
+}
+
+impl ProbeStat {
+    pub const ALL: [Self; 7] = [Self::Speed, Self::Nav, Self::Rep, Self::Haz, Self::Fac, Self::Harv, Self::Wire];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Speed => "Speed",
+            Self::Nav => "Exploration",
+            Self::Rep => "Self-Replication",
+            Self::Haz => "Hazard Remediation",
+            Self::Fac => "Factory Production",
+            Self::Harv => "Harvester Drone Production",
+            Self::Wire => "Wire Drone Production",
+        }
+    }
 }
 
 impl PaperClips {
+    /// # makeProbe
+    pub fn make_probe(&mut self) {
+        self.business.unused_clips -= PROBE_COST;
+        self.space.probe_launch_level += 1.0;
+        self.space.probe_count += 1.0;
+    }
+
+    /// # increaseProbeTrust
+    pub fn increase_probe_trust(&mut self) {
+        self.strategy.yomi -= self.space.probe_trust_cost;
+        self.space.probe_trust += 1.0;
+        self.space.update_probe_trust_cost();
+    }
+    /// # increaseMaxTrust
+    pub fn increase_max_trust(&mut self) {
+        self.combat.honor -= self.space.max_trust_cost as Honor;
+        self.space.max_trust += 10.0;
+        self.space.update_max_trust_cost();
+    }
+
     pub fn encounter_hazards(&mut self) {
         let boost = self.space.probe_haz.powf(1.6);
         let amount = self.space.probe_count * PROBE_HAZ_BASE_RATE / (3.0 * boost + 1.0);
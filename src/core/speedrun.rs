@@ -0,0 +1,252 @@
+use std::{cmp::Ordering, fmt::{self, Display}, str::FromStr, thread};
+
+use crate::{
+    Float, PaperClips, Ticks,
+    player::{ClipperBuyer, Investor, Player, PriceOptimiser, ProjectBuyer, Team, TourneyRunner},
+    progression::Progression,
+    replay::Replay,
+    rng::PCRng,
+};
+
+/// What a speedrun tries to reach
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// `milestones` went past this one, e.g. 6 is "Full autonomy attained"
+    Milestone(u8),
+    Progression(Progression),
+}
+
+impl Goal {
+    pub fn reached(self, pc: &PaperClips) -> bool {
+        match self {
+            Goal::Milestone(milestone) => pc.milestones > milestone,
+            Goal::Progression(progression) => pc.progression >= progression,
+        }
+    }
+}
+
+impl Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Goal::Milestone(milestone) => write!(f, "milestone {milestone}"),
+            Goal::Progression(progression) => write!(f, "{progression}"),
+        }
+    }
+}
+
+/// `milestone:<N>`, `post-human`, `space`, `endgame` or `credits`
+impl FromStr for Goal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(milestone) = s.strip_prefix("milestone:") {
+            return milestone.parse().map(Goal::Milestone).map_err(|e| format!("invalid milestone `{milestone}`: {e}"))
+        }
+        Ok(Goal::Progression(match s {
+            "post-human" => Progression::PostHuman,
+            "space" => Progression::Space,
+            "endgame" => Progression::Endgame,
+            "credits" => Progression::Credits,
+            _ => return Err(format!("unknown goal `{s}`")),
+        }))
+    }
+}
+
+/// The knobs of the reference bots, what the search tunes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Policy {
+    pub price_interval: Ticks,
+    pub stock_secs: Float,
+    pub click_interval: Ticks,
+    pub wire_reserve: Float,
+    pub ad_weight: Float,
+    pub memory_per_processor: u32,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        let price = PriceOptimiser::default();
+        let clipper = ClipperBuyer::default();
+        let project = ProjectBuyer::default();
+        Self {
+            price_interval: price.interval,
+            stock_secs: price.stock_secs,
+            click_interval: clipper.click_interval,
+            wire_reserve: clipper.wire_reserve,
+            ad_weight: clipper.ad_weight,
+            memory_per_processor: project.memory_per_processor,
+        }
+    }
+}
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "price every {} ticks, {:.1}s of stock, a click every {} ticks, wire under {:.2} spools, ads weighted {:.3}, {} memory per processor",
+            self.price_interval, self.stock_secs, self.click_interval, self.wire_reserve, self.ad_weight, self.memory_per_processor,
+        )
+    }
+}
+
+impl Policy {
+    pub fn team(&self) -> Team {
+        Team {
+            players: vec![
+                Box::new(ClipperBuyer { click_interval: self.click_interval, wire_reserve: self.wire_reserve, ad_weight: self.ad_weight }),
+                Box::new(PriceOptimiser { interval: self.price_interval, stock_secs: self.stock_secs }),
+                Box::new(ProjectBuyer { memory_per_processor: self.memory_per_processor, ..Default::default() }),
+                Box::new(TourneyRunner),
+                Box::new(Investor::default()),
+            ],
+        }
+    }
+    /// Scales every knob by a random factor around 1, `sigma` is the standard deviation of its log
+    pub fn mutate(&self, rng: &mut PCRng, sigma: Float) -> Self {
+        let mut scale = |value: Float| value * (normal(rng) * sigma).exp();
+        Self {
+            price_interval: scale(self.price_interval as Float).round().max(1.0) as Ticks,
+            stock_secs: scale(self.stock_secs),
+            click_interval: scale(self.click_interval as Float).round().max(1.0) as Ticks,
+            wire_reserve: scale(self.wire_reserve).clamp(0.01, 1.0),
+            ad_weight: scale(self.ad_weight),
+            // scaling can't get it back from 0
+            memory_per_processor: scale(self.memory_per_processor.max(1) as Float).round() as u32,
+        }
+    }
+}
+
+/// Box–Muller
+fn normal(rng: &mut PCRng) -> Float {
+    let u = 1.0 - rng.random_float_no_best();
+    let v = rng.random_float_no_best();
+    (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+}
+
+/// How a policy did
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Evaluation {
+    pub policy: Policy,
+    /// The tick the goal got reached on
+    pub reached: Option<Ticks>,
+    /// How far the run got when it stopped, to rank the runs that didn't reach the goal
+    pub progress: Float,
+}
+
+impl Evaluation {
+    /// Faster runs first, then the ones that got the furthest
+    pub fn rank(&self, other: &Self) -> Ordering {
+        match (self.reached, other.reached) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => other.progress.total_cmp(&self.progress),
+        }
+    }
+}
+
+/// Plays `policy` from `initial` until `goal` is reached, or `max_ticks` have passed
+pub fn evaluate(initial: &PaperClips, policy: Policy, goal: Goal, max_ticks: Ticks) -> Evaluation {
+    let mut pc = initial.clone();
+    let mut team = policy.team();
+    let end = initial.ticks + max_ticks;
+    while !goal.reached(&pc) && pc.ticks < end {
+        pc.step_with(&mut team);
+    }
+    Evaluation {
+        policy,
+        reached: goal.reached(&pc).then_some(pc.ticks - initial.ticks),
+        progress: pc.milestones as Float + pc.business.clips.max(1.0).log10() / 100.0,
+    }
+}
+
+/// Plays `policy` again, recording every action it takes
+pub fn record(initial: &PaperClips, policy: Policy, goal: Goal, max_ticks: Ticks) -> Replay {
+    let mut pc = initial.clone();
+    let mut replay = Replay::new(initial.clone());
+    let mut team = policy.team();
+    let end = initial.ticks + max_ticks;
+    while !goal.reached(&pc) && pc.ticks < end {
+        replay.step(&mut pc);
+        for action in team.play(&pc) {
+            let _ = replay.record(&mut pc, action);
+        }
+    }
+    replay
+}
+
+/// A (μ + λ) evolution strategy over [`Policy`]
+#[derive(Debug, Clone, Copy)]
+pub struct Search {
+    pub goal: Goal,
+    /// Runs that haven't reached the goal by then are stopped
+    pub max_ticks: Ticks,
+    /// How many new policies get tried every generation
+    pub population: usize,
+    /// How many of the best policies are kept as parents
+    pub elites: usize,
+    pub generations: usize,
+    /// See [`Policy::mutate`]
+    pub sigma: Float,
+    pub threads: usize,
+    pub seed: u64,
+}
+
+impl Search {
+    pub fn new(goal: Goal, max_ticks: Ticks) -> Self {
+        Self {
+            goal,
+            max_ticks,
+            population: 32,
+            elites: 8,
+            generations: 10,
+            sigma: 0.3,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            seed: 0,
+        }
+    }
+
+    /// Evaluates `policies` on all the threads, runs that can't beat `cutoff` are stopped early
+    fn evaluate_all(&self, initial: &PaperClips, policies: &[Policy], cutoff: Ticks) -> Vec<Evaluation> {
+        let chunk = policies.len().div_ceil(self.threads.max(1)).max(1);
+        thread::scope(|scope| {
+            let handles: Vec<_> = policies
+                .chunks(chunk)
+                .map(|chunk| scope.spawn(move || {
+                    chunk.iter().map(|&policy| evaluate(initial, policy, self.goal, cutoff)).collect::<Vec<_>>()
+                }))
+                .collect();
+            handles.into_iter().flat_map(|handle| handle.join().expect("evaluation thread panicked")).collect()
+        })
+    }
+
+    /// Returns the best policy found, `on_generation` gets the best one after every generation
+    pub fn run(&self, initial: &PaperClips, mut on_generation: impl FnMut(usize, &Evaluation)) -> Evaluation {
+        let mut rng = PCRng::seeded(self.seed);
+        let elites = self.elites.clamp(1, self.population.max(1));
+
+        let mut policies = vec![Policy::default()];
+        policies.extend((1..self.population).map(|_| Policy::default().mutate(&mut rng, self.sigma)));
+        let mut best = self.evaluate_all(initial, &policies, self.max_ticks);
+        best.sort_by(Evaluation::rank);
+        best.truncate(elites);
+        on_generation(0, &best[0]);
+
+        for generation in 1..=self.generations {
+            let policies: Vec<_> = (0..self.population)
+                .map(|_| {
+                    let parent = best[(rng.random_float_no_best() * best.len() as Float) as usize % best.len()];
+                    parent.policy.mutate(&mut rng, self.sigma)
+                })
+                .collect();
+            let cutoff = best[0].reached.unwrap_or(self.max_ticks);
+            best.extend(self.evaluate_all(initial, &policies, cutoff));
+            // stable, so older policies win ties
+            best.sort_by(Evaluation::rank);
+            best.truncate(elites);
+            on_generation(generation, &best[0]);
+        }
+
+        best[0]
+    }
+}
@@ -0,0 +1,150 @@
+use std::{fmt::{self, Display}, time::Duration};
+
+use strum::{EnumCount, EnumIter};
+
+use crate::{Float, PaperClips, Ticks, util::ticks_10ms};
+
+/// How often the stats get sampled, until they get downsampled
+pub const STATS_INTERVAL: Ticks = ticks_10ms(Duration::from_secs(1));
+/// How many samples are kept before every other one gets dropped
+pub const MAX_SAMPLES: usize = 1000;
+
+/// A value that gets recorded over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumCount, EnumIter)]
+pub enum Stat {
+    Clips,
+    Funds,
+    UnsoldClips,
+    Demand,
+    WirePrice,
+    Operations,
+    Creativity,
+    Yomi,
+    Probes,
+    Drifters,
+}
+
+impl Stat {
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Clips, Self::Funds, Self::UnsoldClips, Self::Demand, Self::WirePrice,
+        Self::Operations, Self::Creativity, Self::Yomi, Self::Probes, Self::Drifters,
+    ];
+
+    /// The name of its column in the [`Telemetry`](crate::telemetry::Telemetry)
+    pub const fn column(self) -> &'static str {
+        match self {
+            Stat::Clips => "clips",
+            Stat::Funds => "funds",
+            Stat::UnsoldClips => "unsold_clips",
+            Stat::Demand => "demand",
+            Stat::WirePrice => "wire_cost",
+            Stat::Operations => "operations",
+            Stat::Creativity => "creativity",
+            Stat::Yomi => "yomi",
+            Stat::Probes => "probe_count",
+            Stat::Drifters => "drifter_count",
+        }
+    }
+    pub fn value(self, pc: &PaperClips) -> Float {
+        match self {
+            Stat::Clips => pc.business.clips,
+            Stat::Funds => pc.business.funds,
+            Stat::UnsoldClips => pc.business.unsold_clips,
+            Stat::Demand => pc.business.demand,
+            Stat::WirePrice => pc.wire.cost,
+            Stat::Operations => pc.computational.operations,
+            Stat::Creativity => pc.computational.creativity,
+            Stat::Yomi => pc.strategy.yomi,
+            Stat::Probes => pc.space.probe_count,
+            Stat::Drifters => pc.space.drifter_count,
+        }
+    }
+}
+
+impl Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stat::Clips => "Clips",
+            Stat::Funds => "Funds",
+            Stat::UnsoldClips => "Unsold clips",
+            Stat::Demand => "Demand",
+            Stat::WirePrice => "Wire price",
+            Stat::Operations => "Operations",
+            Stat::Creativity => "Creativity",
+            Stat::Yomi => "Yomi",
+            Stat::Probes => "Probes",
+            Stat::Drifters => "Drifters",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub ticks: Ticks,
+    pub values: [Float; Stat::COUNT],
+}
+
+impl Sample {
+    #[inline]
+    pub fn get(&self, stat: Stat) -> Float {
+        self.values[stat as usize]
+    }
+}
+
+/// Every [`Stat`], sampled every `interval` ticks.
+///
+/// Once [`MAX_SAMPLES`] are reached, every other sample gets dropped and the interval doubles,
+/// so a run of any length fits. Like the rates, stats aren't saved.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub interval: Ticks,
+    pub samples: Vec<Sample>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            interval: STATS_INTERVAL,
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl Stats {
+    /// Samples that don't land on the interval, e.g. right after downsampling, are dropped
+    pub fn push(&mut self, sample: Sample) {
+        if self.samples.len() >= MAX_SAMPLES {
+            self.downsample();
+        }
+        if sample.ticks.is_multiple_of(self.interval) {
+            self.samples.push(sample);
+        }
+    }
+    /// Doubles the interval, only keeping the samples that land on it
+    pub fn downsample(&mut self) {
+        self.interval *= 2;
+        let interval = self.interval;
+        self.samples.retain(|sample| sample.ticks.is_multiple_of(interval));
+    }
+    /// `(ticks, value)` of every sample
+    pub fn series(&self, stat: Stat) -> impl Iterator<Item = (Ticks, Float)> + '_ {
+        self.samples.iter().map(move |sample| (sample.ticks, sample.get(stat)))
+    }
+}
+
+impl PaperClips {
+    /// Every [`Stat`] right now, the rows of the [`Telemetry`](crate::telemetry::Telemetry) start with one too
+    pub fn sample(&self) -> Sample {
+        Sample {
+            ticks: self.ticks,
+            values: Stat::ALL.map(|stat| stat.value(self)),
+        }
+    }
+    /// Should run at the end of every main tick
+    pub fn track_stats(&mut self) {
+        if self.ticks.is_multiple_of(self.stats.interval) {
+            let sample = self.sample();
+            self.stats.push(sample);
+        }
+    }
+}
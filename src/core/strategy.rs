@@ -1,13 +1,14 @@
 use std::time::Duration;
 
 use arrayvec::ArrayVec;
+use serde::{Deserialize, Serialize};
 
-use crate::{Float, PaperClips, Ticks, project::PROJECT_128, rng::PCRng, strategy::strategies::{RANDOM, STRAT_COUNT, Strat}, util::ticks_10ms};
+use crate::{Float, PaperClips, Ticks, event::GameEvent, progression::Progression, project::PROJECT_128, rng::PCRng, strategy::strategies::{RANDOM, STRAT_COUNT, Strat}, util::ticks_10ms};
 
 pub mod strategies;
 pub mod util;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum TourneyDisplay {
     RunTournament,
     /// true => "payoff"
@@ -15,7 +16,8 @@ pub enum TourneyDisplay {
     Round,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StrategyGrid {
     pub aa: u8,
     pub ab: u8,
@@ -25,6 +27,7 @@ pub struct StrategyGrid {
     pub v_move: Move,
     pub h_move_prev: Move,
     pub v_move_prev: Move,
+    #[serde(with = "choice_names")]
     pub choice_names: (&'static str, &'static str),
 }
 
@@ -109,7 +112,23 @@ pub const CHOICE_NAMES: &[(&str, &str)] = &[
     ("attack", "decay"),
 ]; 
 
-#[derive(Debug, Clone, Copy)]
+/// Choice names are saved by their index in `CHOICE_NAMES`
+mod choice_names {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    use super::CHOICE_NAMES;
+
+    pub fn serialize<S: Serializer>(names: &(&'static str, &'static str), serializer: S) -> Result<S::Ok, S::Error> {
+        CHOICE_NAMES.iter().position(|n| n == names).unwrap_or(0).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(&'static str, &'static str), D::Error> {
+        let index = usize::deserialize(deserializer)?;
+        CHOICE_NAMES.get(index).copied().ok_or_else(|| D::Error::custom(format!("unknown choice names index {index}")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Move {
     A = 1,
     B = 2,
@@ -137,7 +156,8 @@ pub enum Side {
     BB = 4,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Strategy {
     pub engine_flag: bool,
 
@@ -228,7 +248,7 @@ impl Strategy {
     }
     #[inline]
     pub fn reset_strats(&mut self) {
-        self.strats.sort_by(|a, b| a.0.index.cmp(&b.0.index));
+        self.strats.sort_by_key(|s| s.0.index);
         self.strats.iter_mut().for_each(|(_, cs)| *cs = 0);
     }
     #[inline]
@@ -241,7 +261,7 @@ impl Strategy {
     }
     #[inline]
     pub fn pick_winner(&mut self) {
-        self.strats.sort_by(|a, b| b.1.cmp(&a.1));
+        self.strats.sort_by_key(|s| std::cmp::Reverse(s.1));
     }
     #[inline]
     pub fn picked_strat(&mut self) -> (&'static Strat, u16) {
@@ -377,8 +397,8 @@ impl PaperClips {
         let yomi_increse = picked_score as Float * self.strategy.yomi_boost * beat_boost as Float;
         self.strategy.yomi += yomi_increse;
 
-        if self.milestone_flag < 15 {
-            self.console.push(format!(
+        if self.progression < Progression::Endgame {
+            self.log(format!(
                 "{} scored {picked_score} and beat {bb} {strat_s}. Yomi increased by {yomi_increse:.0}",
                 picked_strat.name,
             ));
@@ -393,17 +413,20 @@ impl PaperClips {
             _ => None,
         };
 
-        match (self.projects.is_active(PROJECT_128), result) {
+        let yomi = match (self.projects.is_active(PROJECT_128), result) {
             (true, Some((yomi_reward, text))) => {
                 self.strategy.yomi += yomi_reward;
-                if self.milestone_flag < 15 {
-                    self.console.push(text);
+                if self.progression < Progression::Endgame {
+                    self.log(text);
                 }
+                yomi_increse + yomi_reward
             }
             _ => {
                 self.strategy.tourney_report(TourneyDisplay::Results(false));
+                yomi_increse
             }
-        } 
+        };
+        self.emit(GameEvent::TourneyResult { strat: picked_strat, score: picked_score, place, yomi });
 
         // populateTourneyReport(); // this is entirely done at runtime
         self.strategy.display_tourney_report();
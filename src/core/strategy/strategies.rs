@@ -1,3 +1,5 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
 use crate::{core::strategy::{Move::{self, *}, Position, Side::*, StrategyGrid, util::{find_biggest_payoff, what_beats_last}}, rng::PCRng};
 
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +15,20 @@ impl PartialEq for Strat {
     }
 }
 
+/// Strats are saved by their `index` in `ALL_STRATS`
+impl Serialize for Strat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.index.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for &'static Strat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = usize::deserialize(deserializer)?;
+        ALL_STRATS.get(index).ok_or_else(|| D::Error::custom(format!("unknown strat index {index}")))
+    }
+}
+
 macro_rules! strats {
     (@gen $i:expr; ) => {};
     (@gen $i:expr; $name:ident { $(# $str:literal)? $($prop:ident: $val:expr),* $(,)? } $($rest:tt)*) => {
@@ -0,0 +1,206 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+use strum::EnumCount;
+
+use crate::{Float, PaperClips, Ticks, progression::Progression, stats::{Sample, Stat}};
+
+/// How often a row gets recorded by default, every second of game time
+pub const DEFAULT_TELEMETRY_INTERVAL: Ticks = 100;
+
+/// How the rows get exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryFormat {
+    /// A header line with [`TelemetryRow::columns`], then one line per row
+    Csv,
+    /// An array of [`TelemetryRow`]
+    Json,
+}
+
+impl TelemetryFormat {
+    #[inline]
+    pub fn extension(self) -> &'static str {
+        match self {
+            TelemetryFormat::Csv => "csv",
+            TelemetryFormat::Json => "json",
+        }
+    }
+    /// `.json` files get [`TelemetryFormat::Json`], anything else [`TelemetryFormat::Csv`]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            true => TelemetryFormat::Json,
+            false => TelemetryFormat::Csv,
+        }
+    }
+}
+
+macro_rules! telemetry {
+    ($($column:ident: $value:expr,)*) => {
+        /// The columns after the ones of every [`Stat`]
+        pub const TELEMETRY_COLUMNS: &[&str] = &[$(stringify!($column)),*];
+
+        /// The key values of the game on one tick, a [`Sample`] of every [`Stat`] and a few more
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct TelemetryRow {
+            pub ticks: Ticks,
+            pub progression: Progression,
+            /// See [`TelemetryRow::get`]
+            pub stats: [Float; Stat::COUNT],
+            $(pub $column: Float,)*
+        }
+
+        impl TelemetryRow {
+            pub fn new(pc: &PaperClips) -> Self {
+                let Sample { ticks, values } = pc.sample();
+                Self {
+                    ticks,
+                    progression: pc.progression,
+                    stats: values,
+                    $($column: ($value)(pc) as Float,)*
+                }
+            }
+            fn write_csv(&self, s: &mut String) {
+                let _ = write!(s, "{},{:?}", self.ticks, self.progression);
+                for value in self.stats.into_iter()$(.chain([self.$column]))* {
+                    s.push(',');
+                    write_float(s, value);
+                }
+                s.push('\n');
+            }
+        }
+
+        impl Serialize for TelemetryRow {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut row = serializer.serialize_struct("TelemetryRow", 2 + Stat::COUNT + TELEMETRY_COLUMNS.len())?;
+                row.serialize_field("ticks", &self.ticks)?;
+                row.serialize_field("progression", &self.progression)?;
+                for stat in Stat::ALL {
+                    row.serialize_field(stat.column(), &self.get(stat))?;
+                }
+                $(row.serialize_field(stringify!($column), &self.$column)?;)*
+                row.end()
+            }
+        }
+    };
+}
+
+// every `Stat` comes first, see `TelemetryRow::new`
+telemetry! {
+    // Business
+    unused_clips: |pc: &PaperClips| pc.business.unused_clips,
+    margin: |pc: &PaperClips| pc.business.margin,
+    marketing_lvl: |pc: &PaperClips| pc.business.marketing_lvl,
+    avg_sales: |pc: &PaperClips| pc.business.avg_sales,
+    avg_rev: |pc: &PaperClips| pc.business.avg_rev,
+    clip_rate: |pc: &PaperClips| pc.business.clip_rate,
+    clipper_level: |pc: &PaperClips| pc.business.clipper_level,
+    mega_clipper_level: |pc: &PaperClips| pc.business.mega_clipper_level,
+    // Wire
+    wire: |pc: &PaperClips| pc.wire.count,
+    wire_supply: |pc: &PaperClips| pc.wire.supply,
+    // Computational
+    trust: |pc: &PaperClips| pc.computational.trust,
+    processors: |pc: &PaperClips| pc.computational.processors,
+    memory: |pc: &PaperClips| pc.computational.memory,
+    // Investments
+    bankroll: |pc: &PaperClips| pc.investments.bankroll,
+    securities: |pc: &PaperClips| pc.investments.sec_total(),
+    invest_level: |pc: &PaperClips| pc.investments.invest_level,
+    // Factory
+    factory_level: |pc: &PaperClips| pc.factory.factory_level,
+    harvester_level: |pc: &PaperClips| pc.factory.harvester_level,
+    wire_drone_level: |pc: &PaperClips| pc.factory.wire_drone_level,
+    farm_level: |pc: &PaperClips| pc.factory.farm_level,
+    battery_level: |pc: &PaperClips| pc.factory.battery_level,
+    stored_power: |pc: &PaperClips| pc.factory.stored_power,
+    swarm_gifts: |pc: &PaperClips| pc.factory.swarm_gifts,
+    // Space
+    available_matter: |pc: &PaperClips| pc.space.available_matter,
+    found_matter: |pc: &PaperClips| pc.space.found_matter,
+    acquired_matter: |pc: &PaperClips| pc.space.acquired_matter,
+    probe_trust: |pc: &PaperClips| pc.space.probe_trust,
+    probes_lost_haz: |pc: &PaperClips| pc.space.probes_lost_haz,
+    probes_lost_drift: |pc: &PaperClips| pc.space.probes_lost_drift,
+    probes_lost_combat: |pc: &PaperClips| pc.space.probes_lost_combat,
+    drifters_killed: |pc: &PaperClips| pc.space.drifters_killed,
+    honor: |pc: &PaperClips| pc.combat.honor,
+}
+
+impl TelemetryRow {
+    #[inline]
+    pub fn get(&self, stat: Stat) -> Float {
+        self.stats[stat as usize]
+    }
+    /// Every column after `ticks` and `progression`
+    pub fn columns() -> impl Iterator<Item = &'static str> {
+        Stat::ALL.into_iter().map(Stat::column).chain(TELEMETRY_COLUMNS.iter().copied())
+    }
+}
+
+/// Huge numbers get an exponent instead of dozens of digits
+fn write_float(s: &mut String, value: Float) {
+    // no `-0`
+    let value = value + 0.0;
+    let _ = match value.abs() < 1e15 {
+        true => write!(s, "{value}"),
+        false => write!(s, "{value:e}"),
+    };
+}
+
+/// A [`TelemetryRow`] every `interval` ticks, for comparing runs outside of the game
+#[derive(Debug, Clone)]
+pub struct Telemetry {
+    pub interval: Ticks,
+    pub rows: Vec<TelemetryRow>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::new(DEFAULT_TELEMETRY_INTERVAL)
+    }
+}
+
+impl Telemetry {
+    pub fn new(interval: Ticks) -> Self {
+        Self {
+            interval: interval.max(1),
+            rows: Vec::new(),
+        }
+    }
+    /// Records a row if `pc` is on the interval, should run after every step
+    pub fn record(&mut self, pc: &PaperClips) {
+        if pc.ticks.is_multiple_of(self.interval) && self.rows.last().is_none_or(|row| row.ticks != pc.ticks) {
+            self.rows.push(TelemetryRow::new(pc));
+        }
+    }
+
+    /// Doubles the interval and drops the rows that aren't on it anymore, for long runs that need to stay small
+    pub fn thin(&mut self) {
+        self.interval *= 2;
+        self.rows.retain(|row| row.ticks.is_multiple_of(self.interval));
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut s = String::from("ticks,progression");
+        for column in TelemetryRow::columns() {
+            s.push(',');
+            s.push_str(column);
+        }
+        s.push('\n');
+        for row in &self.rows {
+            row.write_csv(&mut s);
+        }
+        s
+    }
+    pub fn to_json(&self) -> io::Result<String> {
+        Ok(serde_json::to_string_pretty(&self.rows)?)
+    }
+    /// The format is picked from the extension, see [`TelemetryFormat::from_path`]
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, match TelemetryFormat::from_path(path) {
+            TelemetryFormat::Csv => self.to_csv(),
+            TelemetryFormat::Json => self.to_json()?,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Display, time::{Duration, Instant}};
+use std::{fmt::Display, time::Duration};
 
 use crate::{PaperClips, Ticks, core::Float};
 
@@ -133,8 +133,8 @@ const BLINK_INTERVAL: Ticks = 30;
 const MAX_BLINK_DURATION: Ticks = BLINK_INTERVAL * 12;
 
 /// Returns if the element should be enabled/normal
-pub fn blink(instant: Instant) -> bool {
-    let millis = instant.elapsed().as_millis();
+pub fn blink(elapsed: Duration) -> bool {
+    let millis = elapsed.as_millis();
     if millis > MAX_BLINK_DURATION {
         return true
     }
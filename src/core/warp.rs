@@ -0,0 +1,96 @@
+use std::{fmt::{self, Display}, time::{Duration, Instant}};
+
+use strum::EnumIter;
+
+use crate::{MAIN_TICK, PaperClips, Ticks, util::{ticks_10ms, ticks_to_duration, time_cruncher}};
+
+/// How long catching up on the time away may take, the rest gets skipped
+pub const OFFLINE_BUDGET: Duration = Duration::from_secs(2);
+
+/// How fast the game runs compared to real time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+pub enum Speed {
+    #[default]
+    X1,
+    X10,
+    X100,
+    /// As many ticks as the frame budget allows
+    Max,
+}
+
+impl Speed {
+    /// Real time between two ticks, zero for [`Speed::Max`]
+    pub fn tick_interval(self) -> Duration {
+        match self {
+            Speed::X1 => MAIN_TICK,
+            Speed::X10 => MAIN_TICK / 10,
+            Speed::X100 => MAIN_TICK / 100,
+            Speed::Max => Duration::ZERO,
+        }
+    }
+}
+
+impl Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Speed::X1 => "1x",
+            Speed::X10 => "10x",
+            Speed::X100 => "100x",
+            Speed::Max => "Max",
+        })
+    }
+}
+
+/// What [`PaperClips::catch_up`] did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CatchUp {
+    /// Ticks that got played like any other
+    pub simulated: Ticks,
+    /// Ticks that got [`PaperClips::skip`]ped
+    pub skipped: Ticks,
+}
+
+impl Display for CatchUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |ticks| match time_cruncher(ticks_to_duration(ticks)) {
+            time if time.is_empty() => "0 seconds ".to_string(),
+            time => time,
+        };
+        write!(f, "{}simulated", time(self.simulated))?;
+        if self.skipped > 0 {
+            write!(f, ", {}of operations, creativity and wire price only", time(self.skipped))?;
+        }
+        Ok(())
+    }
+}
+
+impl PaperClips {
+    /// Advances the game by `ticks` without playing them: only the operations, the creativity
+    /// and the wire price move, in closed form. Nothing gets made, sold or bought.
+    pub fn skip(&mut self, ticks: Ticks) {
+        if self.computational.comp_flag {
+            self.computational.advance(ticks);
+        }
+        // see `update_wire_price_and_demand_tick`
+        let every = ticks_10ms(Duration::from_millis(100));
+        let adjustments = (self.ticks + ticks) / every - self.ticks / every;
+        self.wire.adjust_wire_price_n(&mut self.rng, adjustments);
+
+        self.ticks += ticks;
+        if self.qchips.q_flag {
+            self.quantum_compute_update();
+        }
+    }
+    /// Plays `ticks` ticks, or as many as `budget` allows and [`PaperClips::skip`]s the rest
+    pub fn catch_up(&mut self, ticks: Ticks, budget: Duration) -> CatchUp {
+        let start = Instant::now();
+        let mut simulated = 0;
+        while simulated < ticks && start.elapsed() < budget {
+            self.step();
+            simulated += 1;
+        }
+        let skipped = ticks - simulated;
+        self.skip(skipped);
+        CatchUp { simulated, skipped }
+    }
+}
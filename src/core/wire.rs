@@ -1,8 +1,17 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{core::{Float, PaperClips}, rng::PCRng};
+use crate::{core::{Float, PaperClips, Ticks}, rng::PCRng};
+
+/// # `wirePriceTimer` reaching this lowers `wireBasePrice`
+const PRICE_DECAY_TIMER: Ticks = 250;
+const PRICE_DECAY: Float = 0.999;
+/// `wireBasePrice` doesn't decay below this
+const MIN_BASE_PRICE: Float = 15.0;
+/// Chance for the price to change on every adjustment
+const PRICE_CHANGE_CHANCE: Float = 0.015;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Wire {
     /// # wirePriceTimer
     pub price_timer: u8,
@@ -49,8 +58,8 @@ impl Wire {
     pub fn adjust_wire_price(&mut self, rng: &mut PCRng) {
         self.price_timer = self.price_timer.saturating_add(1);
 
-        if self.price_timer >= 250 && self.base_price > 15.0 {
-            self.base_price *= 0.999; 
+        if self.price_timer as Ticks >= PRICE_DECAY_TIMER && self.base_price > MIN_BASE_PRICE {
+            self.base_price *= PRICE_DECAY; 
             self.price_timer = 0;
         }
 
@@ -60,11 +69,52 @@ impl Wire {
         let best_rng = rng.is_best() && new_cost < self.cost;
         let worst_rng = rng.is_worst() && new_cost > self.cost;
         
-        if best_rng || worst_rng || rng.random_bool(0.015, false) {
+        if best_rng || worst_rng || rng.random_bool(PRICE_CHANGE_CHANCE, false) {
             self.price_counter += 1;
             self.cost = new_cost;
         }
     }
+    /// Same as calling [`Wire::adjust_wire_price`] `adjustments` times, with a single roll per price change.
+    ///
+    /// The price follows the same distribution, but not the same rng sequence.
+    /// The best and worst rng don't roll, so they adjust one at a time.
+    pub fn adjust_wire_price_n(&mut self, rng: &mut PCRng, mut adjustments: Ticks) {
+        if rng.is_best() || rng.is_worst() {
+            for _ in 0..adjustments {
+                self.adjust_wire_price(rng);
+            }
+            return
+        }
+        loop {
+            // geometric distribution, the change happens on adjustment `next`
+            let roll = 1.0 - rng.random_float_no_best();
+            let next = (roll.ln() / (1.0 - PRICE_CHANGE_CHANCE).ln()).floor() as Ticks + 1;
+            if next > adjustments {
+                self.decay_base_price(adjustments);
+                return
+            }
+            self.decay_base_price(next);
+            self.cost = self.base_price + 6.0 * (self.price_counter as Float).sin();
+            self.price_counter += 1;
+            adjustments -= next;
+        }
+    }
+    /// What `adjustments` adjustments do to `base_price` and `price_timer`, in closed form
+    fn decay_base_price(&mut self, adjustments: Ticks) {
+        let timer = self.price_timer as Ticks;
+        let first = PRICE_DECAY_TIMER.saturating_sub(timer).max(1);
+        if self.base_price <= MIN_BASE_PRICE || adjustments < first {
+            self.price_timer = (timer + adjustments).min(u8::MAX as Ticks) as u8;
+            return
+        }
+        let possible = 1 + (adjustments - first) / PRICE_DECAY_TIMER;
+        // decays until it's at most `MIN_BASE_PRICE`
+        let needed = ((MIN_BASE_PRICE / self.base_price).ln() / PRICE_DECAY.ln()).ceil().max(1.0) as Ticks;
+        let decays = possible.min(needed);
+        self.base_price *= PRICE_DECAY.powi(decays.min(i32::MAX as Ticks) as i32);
+        let since_last = adjustments - first - (decays - 1) * PRICE_DECAY_TIMER;
+        self.price_timer = since_last.min(u8::MAX as Ticks) as u8;
+    }
 
     // toggle_wire_buyer()
 }
@@ -0,0 +1,99 @@
+use eframe::egui::{Color32, RichText, Ui, Window};
+use egui_extras::{Column, TableBuilder};
+use paperclips::{
+    COMBAT_TICK,
+    combat::history::{WarSummary, Winner},
+    console::format_game_time,
+    util::{number_cruncher, ticks_to_duration},
+};
+
+use crate::gui::Gui;
+
+const TABLE_HEADINGS: &[&str] = &["Battle", "Started", "Probes", "Drifters", "Unit", "Lasted", "Winner", "Honor", "Probes lost", "Drifters killed"];
+const ROW_HEIGHT: f32 = 18.0;
+
+/// The window with every battle of the drift war
+#[derive(Debug, Default)]
+pub struct BattleLog {
+    open: bool,
+}
+
+impl BattleLog {
+    #[inline]
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+fn frames_to_secs(frames: u16) -> String {
+    format!("{:.1}s", frames as f64 * COMBAT_TICK.as_secs_f64())
+}
+
+impl Gui {
+    pub fn draw_battle_log(&mut self, ui: &mut Ui) {
+        let history = &self.paperclips.combat.history;
+
+        Window::new("Battle history")
+            .open(&mut self.battle_log.open)
+            .default_size([800.0, 400.0])
+            .show(ui.ctx(), |ui| {
+                let summary = WarSummary::new(history);
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{} battles: {} won, {} lost, {} drawn", summary.battles, summary.won, summary.lost, summary.drawn));
+                    ui.separator();
+                    ui.label(format!("Honor +{} / -{}", summary.honor_gained, summary.honor_lost));
+                    ui.separator();
+                    ui.label(format!("Probes lost: {}", number_cruncher(summary.probes_lost, None)));
+                    ui.label(format!("Drifters killed: {}", number_cruncher(summary.drifters_killed, None)));
+                    ui.separator();
+                    ui.label(format!("Average battle: {:.1}s", summary.mean_duration * COMBAT_TICK.as_secs_f64()));
+                    ui.label(format!("Largest fleet: {}", summary.largest_fleet));
+                });
+                ui.separator();
+
+                if history.is_empty() {
+                    ui.weak("No battles yet");
+                    return
+                }
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .stick_to_bottom(true)
+                    .column(Column::auto().at_least(120.0))
+                    .columns(Column::auto(), TABLE_HEADINGS.len() - 1)
+                    .header(ROW_HEIGHT, |mut row| {
+                        for col in TABLE_HEADINGS {
+                            row.col(|ui| { ui.strong(*col); });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(ROW_HEIGHT, history.len(), |mut row| {
+                            let record = &history[row.index()];
+                            row.col(|ui| { ui.label(record.name.to_string()); });
+                            row.col(|ui| { ui.label(format_game_time(ticks_to_duration(record.started))); });
+                            row.col(|ui| { ui.label(record.max_ships.0.to_string()); });
+                            row.col(|ui| { ui.label(record.max_ships.1.to_string()); });
+                            row.col(|ui| { ui.label(number_cruncher(record.unit_size, None)); });
+                            row.col(|ui| { ui.label(frames_to_secs(record.duration)); });
+                            row.col(|ui| {
+                                match record.winner {
+                                    Some(winner @ Winner::Probes) => ui.colored_label(Color32::LIGHT_GREEN, winner.to_string()),
+                                    Some(winner @ Winner::Drifters) => ui.colored_label(Color32::LIGHT_RED, winner.to_string()),
+                                    Some(winner @ Winner::Draw) => ui.label(winner.to_string()),
+                                    None => ui.label(RichText::new("Ongoing").italics()),
+                                };
+                            });
+                            row.col(|ui| {
+                                match record.honor_change {
+                                    0 => ui.weak("0"),
+                                    change if change > 0 => ui.label(format!("+{change}")),
+                                    change => ui.label(change.to_string()),
+                                };
+                            });
+                            row.col(|ui| { ui.label(number_cruncher(record.probes_lost, None)); });
+                            row.col(|ui| { ui.label(number_cruncher(record.drifters_killed, None)); });
+                        });
+                    });
+            });
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::Duration;
 
 use eframe::egui::{Color32, RichText, Ui};
 use paperclips::Ticks;
@@ -13,8 +13,8 @@ const LONG_BLINK_INTERVAL: Ticks = 32;
 impl Gui {
     /// # hypnoDroneEvent
     /// Returns if the long blink finished
-    pub fn long_blink(&mut self, ui: &mut Ui, start: Instant) -> bool {
-        let time = start.elapsed().as_millis();
+    pub fn long_blink(&mut self, ui: &mut Ui, elapsed: Duration) -> bool {
+        let time = elapsed.as_millis();
 
         let step = time / LONG_BLINK_INTERVAL;
 
@@ -0,0 +1,81 @@
+use eframe::egui::{ScrollArea, Ui, Window};
+use egui_plot::{Line, Plot, PlotPoints};
+use paperclips::{stats::Stat, util::{number_cruncher, ticks_to_secs_float}};
+use strum::{EnumCount, IntoEnumIterator};
+
+use crate::gui::Gui;
+
+const PLOT_HEIGHT: f32 = 150.0;
+
+/// The window plotting the stats over time
+#[derive(Debug)]
+pub struct Charts {
+    open: bool,
+    shown: [bool; Stat::COUNT],
+    /// Plots `log10` of the values, most of them grow exponentially
+    log_scale: bool,
+}
+
+impl Default for Charts {
+    fn default() -> Self {
+        let mut shown = [false; Stat::COUNT];
+        shown[Stat::Clips as usize] = true;
+        shown[Stat::Funds as usize] = true;
+        Self { open: false, shown, log_scale: false }
+    }
+}
+
+impl Charts {
+    #[inline]
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+impl Gui {
+    pub fn draw_charts(&mut self, ui: &mut Ui) {
+        let Charts { open, shown, log_scale } = &mut self.charts;
+        let stats = &self.paperclips.stats;
+
+        Window::new("Statistics")
+            .open(open)
+            .default_size([500.0, 500.0])
+            .show(ui.ctx(), |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for stat in Stat::iter() {
+                        ui.checkbox(&mut shown[stat as usize], stat.to_string());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(log_scale, "Log scale");
+                    ui.weak(format!("{} samples, one every {:.0}s", stats.samples.len(), ticks_to_secs_float(stats.interval)));
+                });
+                ui.separator();
+
+                let log_scale = *log_scale;
+                ScrollArea::vertical().show(ui, |ui| {
+                    for stat in Stat::iter().filter(|&stat| shown[stat as usize]) {
+                        ui.strong(stat.to_string());
+                        let points: PlotPoints = stats
+                            .series(stat)
+                            .map(|(ticks, value)| [ticks_to_secs_float(ticks), if log_scale { value.max(1.0).log10() } else { value }])
+                            .collect();
+                        Plot::new(("stat_plot", stat as usize))
+                            .height(PLOT_HEIGHT)
+                            .link_axis("stats", [true, false])
+                            .link_cursor("stats", [true, false])
+                            .x_axis_label("Game time (s)")
+                            .y_axis_formatter(move |mark, _| match log_scale {
+                                true => format!("1e{:.0}", mark.value),
+                                false => number_cruncher(mark.value, Some(1)),
+                            })
+                            .label_formatter(move |_, point| {
+                                let value = if log_scale { 10f64.powf(point.y) } else { point.y };
+                                format!("{:.0}s\n{}", point.x, number_cruncher(value, Some(2)))
+                            })
+                            .show(ui, |plot| plot.line(Line::new(stat.to_string(), points)));
+                    }
+                });
+            });
+    }
+}
@@ -1,8 +1,8 @@
-use std::{borrow::Cow, time::Instant};
+use std::borrow::Cow;
 
-use eframe::egui::{Color32, ComboBox, CornerRadius, CursorIcon, Frame, InnerResponse, Rect, RichText, Sense, Ui, Vec2};
+use eframe::egui::{Color32, ComboBox, CornerRadius, CursorIcon, Frame, Grid, InnerResponse, Rect, RichText, Sense, Ui, Vec2};
 use egui_extras::{Column, TableBuilder};
-use paperclips::{console::Console, end::Dismantle, investments::Riskiness, qchips::QOPS_FADE_TIME, strategy::TourneyDisplay, util::{blink, number_cruncher}};
+use paperclips::{Float, action::{Action, Cheat}, console::Console, end::Dismantle, investments::Riskiness, project::PROJECT_121, qchips::QOPS_FADE_TIME, rates::Flow, space::{PROBE_COST, ProbeStat, TOTAL_MATTER}, strategy::TourneyDisplay, util::{blink, number_cruncher, ticks_to_duration}};
 use strum::IntoEnumIterator;
 
 use crate::gui::Gui;
@@ -12,16 +12,19 @@ pub mod combat;
 
 impl Gui {
     pub fn draw_make_paperclip(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
-        ui.add_enabled_ui(pc.wire.count >= 1.0, |ui| {
+        let pc = &self.paperclips;
+        ui.add_enabled_ui(pc.check(Action::MakePaperclip).is_ok(), |ui| {
             if ui.button("Make Paperclip").clicked() {
-                pc.clip_click(1.0);
+                self.actions.push(Action::MakePaperclip);
             }
         });
+        if pc.end.dismantle >= Dismantle::Strategy {
+            ui.label(format!("Wire: {}, Final Clips: {}", pc.wire.count, pc.end.final_clips));
+        }
     }
 
     pub fn draw_business_group(&mut self, ui: &mut Ui) -> InnerResponse<()> {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
         
         ui.group(|ui| {
             ui.heading("Business");
@@ -42,13 +45,13 @@ impl Gui {
                 pc.business.unsold_clips
             ));
             ui.horizontal(|ui| {
-                ui.add_enabled_ui(pc.business.margin > 0.01, |ui| {
+                ui.add_enabled_ui(pc.check(Action::LowerPrice).is_ok(), |ui| {
                     if ui.button("lower").clicked() {
-                        pc.business.lower_price();
+                        self.actions.push(Action::LowerPrice);
                     }
                 });
                 if ui.button("raise").clicked() {
-                    pc.business.raise_price();
+                    self.actions.push(Action::RaisePrice);
                 }
                 ui.label(format!(
                     "Price per Clip: ${:.2}",
@@ -63,14 +66,11 @@ impl Gui {
             ui.add_space(10.0);
     
             ui.horizontal(|ui| {
-                ui.add_enabled_ui(
-                    pc.business.funds >= pc.business.ad_cost,
-                    |ui| {
-                        if ui.button("Marketing").clicked() {
-                            pc.business.buy_ads();
-                        }
-                    },
-                );
+                ui.add_enabled_ui(pc.check(Action::BuyAds).is_ok(), |ui| {
+                    if ui.button("Marketing").clicked() {
+                        self.actions.push(Action::BuyAds);
+                    }
+                });
                 ui.label(format!("Level: {}", pc.business.marketing_lvl));
             });
             ui.label(format!("Cost: ${}", pc.business.ad_cost));
@@ -78,7 +78,7 @@ impl Gui {
     }
     
     pub fn draw_manufacturing_group(&mut self, ui: &mut Ui) -> InnerResponse<()> {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         ui.group(|ui| {
             ui.heading("Manufacturing");
@@ -94,7 +94,7 @@ impl Gui {
             if pc.wire.buyer_flag {
                 ui.horizontal(|ui| {
                     if ui.button("WireBuyer").clicked() {
-                        pc.wire.buyer_status ^= true;
+                        self.actions.push(Action::ToggleWireBuyer);
                     }
                     ui.label(match pc.wire.buyer_status {
                         true => "ON",
@@ -103,9 +103,9 @@ impl Gui {
                 });
             }
             ui.horizontal(|ui| {
-                ui.add_enabled_ui(pc.business.funds >= pc.wire.cost, |ui| {
+                ui.add_enabled_ui(pc.check(Action::BuyWire).is_ok(), |ui| {
                     if ui.button("Wire").clicked() {
-                        pc.buy_wire();
+                        self.actions.push(Action::BuyWire);
                     }
                 });
                 ui.label(format!("{:.0} inches", pc.wire.count));
@@ -116,9 +116,9 @@ impl Gui {
                 ui.add_space(10.0);
     
                 ui.horizontal(|ui| {
-                    ui.add_enabled_ui(pc.business.funds >= pc.business.clipper_cost, |ui| {
+                    ui.add_enabled_ui(pc.check(Action::MakeClipper).is_ok(), |ui| {
                         if ui.button("AutoClippers").clicked() {
-                            pc.business.make_clipper();
+                            self.actions.push(Action::MakeClipper);
                         }
                     });
                     ui.label(format!("{:.0}", pc.business.clipper_level));
@@ -130,9 +130,9 @@ impl Gui {
                 ui.add_space(10.0);
     
                 ui.horizontal(|ui| {
-                    ui.add_enabled_ui(pc.business.funds >= pc.business.mega_clipper_cost, |ui| {
+                    ui.add_enabled_ui(pc.check(Action::MakeMegaClipper).is_ok(), |ui| {
                         if ui.button("MegaClippers").clicked() {
-                            pc.business.make_mega_clipper();
+                            self.actions.push(Action::MakeMegaClipper);
                         }
                     });
                     ui.label(format!("{:.0}", pc.business.mega_clipper_level));
@@ -143,7 +143,7 @@ impl Gui {
     }
     
     pub fn draw_computational_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         if pc.computational.comp_flag {
             ui.group(|ui| {
@@ -153,7 +153,7 @@ impl Gui {
                 ui.separator();
 
                 // #trustDiv
-                if pc.human_flag {
+                if pc.human_flag() {
                     ui.label(format!("Trust: {}", c.trust));
                     ui.label(format!("+1 Trust at: {} clips", c.next_trust));
                     ui.add_space(10.0);
@@ -164,14 +164,14 @@ impl Gui {
                     ui.label(format!("Swarm Gifts: {:.0}", pc.factory.swarm_gifts));
                 }
     
-                let enable_compute_trust_buttons = c.trust > c.processors as i32 + c.memory as i32 /* && swarmGifts > 0 */;
+                let enable_compute_trust_buttons = pc.check(Action::AddProcessor).is_ok();
 
                 // #processorDisplay
-                if true /* dismantle >= 6 */ {
+                if pc.end.dismantle < Dismantle::Processors {
                     ui.horizontal(|ui| {
                         ui.add_enabled_ui(enable_compute_trust_buttons, |ui| {
                             if ui.button("Processors").clicked() {
-                                pc.add_processors();
+                                self.actions.push(Action::AddProcessor);
                             }
                         });
                         ui.label(pc.computational.processors.to_string());
@@ -180,7 +180,7 @@ impl Gui {
                 ui.horizontal(|ui| {
                     ui.add_enabled_ui(enable_compute_trust_buttons, |ui| {
                         if ui.button("Memory").clicked() {
-                            pc.add_memory();
+                            self.actions.push(Action::AddMemory);
                         }
                     });
                     ui.label(pc.computational.memory.to_string());
@@ -196,7 +196,7 @@ impl Gui {
     }
     
     pub fn draw_quantum_computing_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         ui.group(|ui| {
             const SIZE: f32 = 20.0;
@@ -217,15 +217,16 @@ impl Gui {
             }
             ui.horizontal(|ui| {
                 if ui.button("Compute").clicked() {
-                    pc.quantum_compute();
+                    self.actions.push(Action::QuantumCompute);
                 }
                 let text = match pc.qchips.qops {
                     Some(qops) => format!("qOps: {qops:.0}"),
                     None => "Need Photonic Chips".to_string(),
                 };
                 let text_color = ui.style().visuals.text_color();
+                let since_fade = pc.qchips.fade.map_or(QOPS_FADE_TIME, |fade| ticks_to_duration(pc.ticks - fade));
                 let transparency = QOPS_FADE_TIME
-                    .saturating_sub(pc.qchips.fade.elapsed())
+                    .saturating_sub(since_fade)
                     .as_secs_f32()
                     / QOPS_FADE_TIME.as_secs_f32();
                 let color = text_color.gamma_multiply(transparency);
@@ -235,15 +236,14 @@ impl Gui {
     }
     
     pub fn draw_projects_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         ui.group(|ui| {
             ui.heading("Projects");
             ui.separator();
     
-            let buyable_projects = pc.projects.buyable_projects.clone().into_iter().enumerate();
-            for (bpi, (instant, project)) in buyable_projects {
-                let affordable = (project.cost.1)(pc);
+            for &(since, project) in &pc.projects.buyable_projects {
+                let affordable = pc.check(Action::BuyProject(project)).is_ok();
     
                 ui.add_enabled_ui(affordable, |ui| {
                     let mut frame = Frame::group(ui.style()).begin(ui);
@@ -261,7 +261,7 @@ impl Gui {
                     if pj.hovered() && affordable && pj.enabled() {
                         frame.frame.stroke.color = Color32::GRAY;
                     }
-                    if !blink(instant) {
+                    if !blink(ticks_to_duration(pc.ticks - since)) {
                         frame.frame.stroke.color = Color32::WHITE;
                     }
                     frame.paint(ui);
@@ -269,7 +269,7 @@ impl Gui {
                     if affordable {
                         let pj = pj.interact(Sense::CLICK).on_hover_cursor(CursorIcon::PointingHand);
                         if pj.clicked() {
-                            pc.buy_project(bpi);
+                            self.actions.push(Action::BuyProject(project));
                         }
                     }
                 });
@@ -278,28 +278,31 @@ impl Gui {
     }
     
     pub fn draw_investments_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         ui.group(|ui| {
             ui.heading("Investments");
             ui.separator();
     
-            let riskiness = &mut pc.investments.riskiness;
+            let mut riskiness = pc.investments.riskiness;
         
             ComboBox::from_label("Riskiness")
                 .selected_text(riskiness.name())
                 .show_ui(ui, |ui| {
                     for risk in Riskiness::iter() {
-                        ui.selectable_value(riskiness, risk, risk.name());
+                        ui.selectable_value(&mut riskiness, risk, risk.name());
                     }
                 });
+            if riskiness != pc.investments.riskiness {
+                self.actions.push(Action::SetRiskiness(riskiness));
+            }
     
             ui.columns_const(|[left, right]| {
                 if left.button("Deposit").clicked() {
-                    pc.invest_deposit();
+                    self.actions.push(Action::InvestDeposit);
                 }
                 if left.button("Withdraw").clicked() {
-                    pc.invest_withdraw();
+                    self.actions.push(Action::InvestWithdraw);
                 }
                 
                 right.label(format!("Cash: ${:.2}", pc.investments.bankroll));
@@ -337,9 +340,9 @@ impl Gui {
                     });
             });
             ui.horizontal(|ui| {
-                ui.add_enabled_ui(pc.strategy.yomi >= pc.investments.invest_upgrade_cost, |ui| {
+                ui.add_enabled_ui(pc.check(Action::InvestUpgrade).is_ok(), |ui| {
                     if ui.button("Upgrade Investment Engine").clicked() {
-                        pc.invest_upgrade();
+                        self.actions.push(Action::InvestUpgrade);
                     }
                 });
                 ui.label(format!("Level: {}", pc.investments.invest_level));
@@ -353,18 +356,22 @@ impl Gui {
             ui.heading("Strategic Modeling");
             ui.separator();
 
-            let pc = &mut self.paperclips;
+            let pc = &self.paperclips;
 
-            ui.add_enabled_ui(pc.strategy.tourney_in_prog && !pc.strategy.disable_run_button, |ui| {
+            ui.add_enabled_ui(pc.check(Action::RunTourney).is_ok(), |ui| {
+                let mut pick = pc.strategy.pick;
                 ComboBox::from_label("Pick a Strat")
-                .selected_text(pc.strategy.pick.name)
+                .selected_text(pick.name)
                 .show_ui(ui, |ui| {
                     for (strat, _) in &pc.strategy.strats {
-                        ui.selectable_value(&mut pc.strategy.pick, strat, strat.name);
+                        ui.selectable_value(&mut pick, strat, strat.name);
                     }
                 });
+                if pick != pc.strategy.pick {
+                    self.actions.push(Action::PickStrat(pick));
+                }
                 if ui.button("Run").clicked() {
-                    pc.run_tourney();
+                    self.actions.push(Action::RunTourney);
                 }
             });
 
@@ -392,13 +399,13 @@ impl Gui {
                 unsafe { HOVERED = resp.hovered(); }
             });
 
-            let pc = &mut self.paperclips;
+            let pc = &self.paperclips;
 
             ui.label(format!("Yomi: {:.0}", pc.strategy.yomi));
             
-            ui.add_enabled_ui(!pc.strategy.tourney_in_prog && pc.computational.operations >= pc.strategy.tourney_cost, |ui| {
+            ui.add_enabled_ui(pc.check(Action::NewTourney).is_ok(), |ui| {
                 if ui.button("New Tournament").clicked() {
-                    pc.new_tourney();
+                    self.actions.push(Action::NewTourney);
                 }
             });
             ui.label(format!("Cost: {:.0} ops", pc.strategy.tourney_cost));
@@ -406,34 +413,36 @@ impl Gui {
     }
 
     pub fn draw_creation_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
         ui.group(|ui| {
             ui.heading("Manufacturing");
             ui.separator();
     
             ui.small(format!("Next Upgrade at: {} Factories", 0)); // TODO
-            ui.label(format!("Clips per Second: {}", 0)); // TODO
+            ui.label(format!("Clips per Second: {}", number_cruncher(pc.rates.per_second(Flow::ClipsCreated), None)));
             ui.label(format!("Unused Clips: {}", pc.business.unused_clips));
     
             ui.add_space(10.0);
     
             // #factoryDiv
             ui.horizontal(|ui| {
-                if ui.button("Clip Factory").clicked() {
-                    // TODO: makeFactory();
-                }
+                ui.add_enabled_ui(pc.check(Action::MakeFactory).is_ok(), |ui| {
+                    if ui.button("Clip Factory").clicked() {
+                        self.actions.push(Action::MakeFactory);
+                    }
+                });
                 ui.label(pc.factory.factory_level.to_string());
             });
             {
                 let resp = ui.button("Disassemble All");
                 if resp.clicked() {
-                    pc.factory_reboot();
+                    self.actions.push(Action::FactoryReboot);
                 }
-                resp.on_hover_text(number_cruncher(pc.factory.factory_level.into(), Some(1)));
+                resp.on_hover_text(number_cruncher(pc.factory.factory_level, Some(1)));
             }
             ui.add_space(10.0);
-            ui.label(format!("Cost: {} clips", 0)); // TODO
+            ui.label(format!("Cost: {} clips", number_cruncher(pc.factory.factory_cost, None)));
             ui.add_space(10.0);
             ui.label(format!("Wire: {} inches", pc.wire.count));
             ui.label(format!("Factories: {}", pc.factory.factory_level));
@@ -447,19 +456,29 @@ impl Gui {
 
             ui.small(format!("Next Upgrade at: {} Drones", 0)); // TODO
 
+            let rates = &self.paperclips.rates;
+            let found = rates.per_second(Flow::MatterFound);
+            let acquired = rates.per_second(Flow::MatterAcquired);
+            let wire = rates.per_second(Flow::WireProduced);
+            // matter usually goes down, `number_cruncher` doesn't shorten negative numbers
+            let signed = |rate: Float| match rate < 0.0 {
+                true => format!("-{}", number_cruncher(-rate, None)),
+                false => number_cruncher(rate, None),
+            };
+
             ui.label(format!("Available Matter: {} g", self.paperclips.space.available_matter));
-            ui.label(format!("({} g per sec)", 0)); // TODO
+            ui.label(format!("({} g per sec)", signed(found - acquired)));
             ui.label(format!("Acquired Matter: {} g", self.paperclips.space.acquired_matter));
-            ui.label(format!("({} g per sec)", 0)); // TODO
+            ui.label(format!("({} g per sec)", signed(acquired - wire)));
             ui.label(format!("Wire: {} inches", self.paperclips.wire.count));
-            ui.label(format!("({} inches per sec)", 0)); // TODO
+            ui.label(format!("({} inches per sec)", number_cruncher(wire, None)));
 
             self.draw_harvester_div(ui);
             ui.add_space(10.0);
             self.draw_wire_drone_div(ui);
 
             // #droneDivSpace
-            if self.paperclips.space.space_flag {
+            if self.paperclips.space_flag() {
                 ui.label(format!("Harvester Drones: {}", self.paperclips.factory.harvester_level));
                 ui.label(format!("Wire Drones: {}", self.paperclips.factory.wire_drone_level));
             }
@@ -468,103 +487,219 @@ impl Gui {
 
     /// # #harvesterDiv
     pub fn draw_harvester_div(&mut self, ui: &mut Ui) {
+        let pc = &self.paperclips;
+
         ui.horizontal(|ui| {
-            if ui.button("Harvester Drone").clicked() {
-                // TODO: makeHarvester(1)
-            }
+            ui.add_enabled_ui(pc.check(Action::MakeHarvester(1)).is_ok(), |ui| {
+                if ui.button("Harvester Drone").clicked() {
+                    self.actions.push(Action::MakeHarvester(1));
+                }
+            });
+            ui.label(pc.factory.harvester_level.to_string());
         });
-        // TODO: disable
         ui.horizontal(|ui| {
             for (title, amount) in [("+10", 10), ("+100", 100), ("+1k", 1000)] {
-                if ui.button(title).clicked() {
-                    // TODO: makeHarvester(amount)
-                }
+                let action = Action::MakeHarvester(amount);
+                ui.add_enabled_ui(pc.check(action).is_ok(), |ui| {
+                    if ui.button(title).clicked() {
+                        self.actions.push(action);
+                    }
+                });
             }
         });
 
         {
             let resp = ui.button("Disassemble All");
             if resp.clicked() {
-                self.paperclips.harvester_reboot();
+                self.actions.push(Action::HarvesterReboot);
             }
-            resp.on_hover_text(number_cruncher(self.paperclips.factory.harvester_level.into(), None));
+            resp.on_hover_text(number_cruncher(pc.factory.harvester_level, None));
         }
 
         ui.add_space(10.0);
 
-        ui.label(format!("Cost: {} clips", 0)); // TODO
+        ui.label(format!("Cost: {} clips", number_cruncher(pc.factory.harvester_cost, None)));
     }
 
     /// # #wireDroneDiv
     pub fn draw_wire_drone_div(&mut self, ui: &mut Ui) {
+        let pc = &self.paperclips;
+
         ui.horizontal(|ui| {
-            if ui.button("Wire Drone").clicked() {
-                // TODO: makeWireDrone(1)
-            }
+            ui.add_enabled_ui(pc.check(Action::MakeWireDrone(1)).is_ok(), |ui| {
+                if ui.button("Wire Drone").clicked() {
+                    self.actions.push(Action::MakeWireDrone(1));
+                }
+            });
+            ui.label(pc.factory.wire_drone_level.to_string());
         });
-        // TODO: disable
         ui.horizontal(|ui| {
             for (title, amount) in [("+10", 10), ("+100", 100), ("+1k", 1000)] {
-                if ui.button(title).clicked() {
-                    // TODO: makeWireDrone(amount)
-                }
+                let action = Action::MakeWireDrone(amount);
+                ui.add_enabled_ui(pc.check(action).is_ok(), |ui| {
+                    if ui.button(title).clicked() {
+                        self.actions.push(action);
+                    }
+                });
             }
         });
 
         {
             let resp = ui.button("Disassemble All");
             if resp.clicked() {
-                self.paperclips.wire_drone_reboot();
+                self.actions.push(Action::WireDroneReboot);
             }
-            resp.on_hover_text(number_cruncher(self.paperclips.factory.wire_drone_level.into(), None));
+            resp.on_hover_text(number_cruncher(pc.factory.wire_drone_level, None));
         }
 
         ui.add_space(10.0);
 
-        ui.label(format!("Cost: {} clips", 0)); // TODO
+        ui.label(format!("Cost: {} clips", number_cruncher(pc.factory.wire_drone_cost, None)));
+    }
+
+    /// # #powerDiv
+    pub fn draw_power_group(&mut self, ui: &mut Ui) {
+        let pc = &self.paperclips;
+        let factory = &pc.factory;
+
+        ui.group(|ui| {
+            ui.heading("Power");
+            ui.separator();
+
+            let demand = factory.power_drone_demand() + factory.power_factory_demand();
+            ui.label(format!("Production: {} MW", number_cruncher(factory.power_supply() * 100.0, None)));
+            ui.label(format!("Consumption: {} MW", number_cruncher(demand * 100.0, None)));
+            ui.label(format!(
+                "Stored: {} / {} MW-s",
+                number_cruncher(factory.stored_power, None),
+                number_cruncher(factory.battery_cap().into(), None),
+            ));
+            ui.label(format!("Performance: {:.0}%", factory.pow_mod * 100.0));
+
+            ui.add_space(10.0);
+
+            for (name, level, cost, make, reboot) in [
+                ("Solar Farm", factory.farm_level, factory.farm_cost, Action::MakeFarm as fn(u32) -> Action, Action::FarmReboot),
+                ("Battery Tower", factory.battery_level, factory.battery_cost, Action::MakeBattery, Action::BatteryReboot),
+            ] {
+                ui.horizontal(|ui| {
+                    for (title, amount) in [(name, 1), ("+10", 10), ("+100", 100)] {
+                        let action = make(amount);
+                        ui.add_enabled_ui(pc.check(action).is_ok(), |ui| {
+                            if ui.button(title).clicked() {
+                                self.actions.push(action);
+                            }
+                        });
+                    }
+                    ui.label(level.to_string());
+                });
+                if ui.button("Disassemble All").clicked() {
+                    self.actions.push(reboot);
+                }
+                ui.label(format!("Cost: {} clips", number_cruncher(cost, None)));
+                ui.add_space(10.0);
+            }
+        });
     }
 
     /// # #spaceDiv
     pub fn draw_space_group(&mut self, ui: &mut Ui) {
+        let pc = &self.paperclips;
+        let space = &pc.space;
+
         ui.group(|ui| {
             ui.heading("Space Exploration");
             ui.separator();
     
-            ui.label(format!("{:.12}% of universe explored", 0)); // TODO
+            ui.label(format!("{:.12}% of universe explored", space.found_matter / TOTAL_MATTER * 100.0));
     
-            if ui.button("Launch Probe").clicked() {
-                // TODO: makeProbe()
-            }
-            ui.label(format!("Cost: {} clips", 0)); // TODO
+            ui.add_enabled_ui(pc.check(Action::MakeProbe).is_ok(), |ui| {
+                if ui.button("Launch Probe").clicked() {
+                    self.actions.push(Action::MakeProbe);
+                }
+            });
+            ui.label(format!("Cost: {} clips", number_cruncher(PROBE_COST, None)));
             
             ui.add_space(10.0);
     
-            ui.label(format!("Launched: {}", 0)); // TODO
-            ui.label(format!("Descendents: {}", 0)); // TODO
+            ui.label(format!("Launched: {}", number_cruncher(space.probe_launch_level, None)));
+            ui.label(format!("Descendents: {}", number_cruncher(space.probe_descendents, None)));
     
             ui.add_space(10.0);
     
-            ui.label(format!("Lost to hazards: ({})", 0)); // TODO
-            ui.label(format!("Lost to value drift: ({})", 0)); // TODO
-            ui.label(format!("Lost in combat: ({})", 0)); // TODO
+            ui.label(format!("Lost to hazards: ({})", number_cruncher(space.probes_lost_haz, None)));
+            ui.label(format!("Lost to value drift: ({})", number_cruncher(space.probes_lost_drift, None)));
+            ui.label(format!("Lost in combat: ({})", number_cruncher(space.probes_lost_combat, None)));
             ui.separator();
-            ui.label(format!("Total: {}", 0)); // TODO
+            ui.label(format!("Total: {}", number_cruncher(space.probe_count, None)));
     
             ui.add_space(10.0);
     
-            ui.label(format!("Drifters Killed: {}", 0)); // TODO
-            ui.label(format!("Drifters: {}", 0)); // TODO
+            ui.label(format!("Drifters Killed: {}", number_cruncher(space.drifters_killed, None)));
+            ui.label(format!("Drifters: {}", number_cruncher(space.drifter_count, None)));
+        });
+    }
+
+    /// # #probeDesignDiv
+    pub fn draw_probe_design_group(&mut self, ui: &mut Ui) {
+        let pc = &self.paperclips;
+        let space = &pc.space;
+
+        ui.group(|ui| {
+            ui.heading("Von Neumann Probe Design");
+            ui.separator();
+
+            ui.label(format!("Trust: {} / {}", space.probe_used_trust, space.probe_trust));
+
+            Grid::new("probe_design").show(ui, |ui| {
+                for stat in ProbeStat::ALL {
+                    ui.add_enabled_ui(pc.check(Action::LowerProbeStat(stat)).is_ok(), |ui| {
+                        if ui.button("-").clicked() {
+                            self.actions.push(Action::LowerProbeStat(stat));
+                        }
+                    });
+                    ui.add_enabled_ui(pc.check(Action::RaiseProbeStat(stat)).is_ok(), |ui| {
+                        if ui.button("+").clicked() {
+                            self.actions.push(Action::RaiseProbeStat(stat));
+                        }
+                    });
+                    ui.label(format!("{}: {}", stat.name(), space.probe_stat(stat)));
+                    ui.end_row();
+                }
+            });
+
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(pc.check(Action::IncreaseProbeTrust).is_ok(), |ui| {
+                    if ui.button("Increase Probe Trust").clicked() {
+                        self.actions.push(Action::IncreaseProbeTrust);
+                    }
+                });
+                ui.label(format!("Cost: {} yomi", number_cruncher(space.probe_trust_cost, None)));
+            });
+            ui.label(format!("Max Trust: {}", space.max_trust));
+            if pc.projects.is_active(PROJECT_121) {
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(pc.check(Action::IncreaseMaxTrust).is_ok(), |ui| {
+                        if ui.button("Increase Max Trust").clicked() {
+                            self.actions.push(Action::IncreaseMaxTrust);
+                        }
+                    });
+                    ui.label(format!("Cost: {} honor", number_cruncher(space.max_trust_cost, None)));
+                });
+            }
         });
     }
 
     pub fn draw_top_console(&mut self, ui: &mut Ui) {
-        if let Some(start) = self.paperclips.space.hypno_drone_event && self.long_blink(ui, start) {
-            self.paperclips.space.hypno_drone_event = None;
+        if let Some(start) = self.paperclips.space.hypno_drone_event {
+            self.long_blink(ui, ticks_to_duration(self.paperclips.ticks - start));
         }
 
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
 
-        let Console { max_messages, messages } = &pc.console;
+        let Console { max_messages, messages, .. } = &pc.console;
         let to_fill = *max_messages - messages.len();
 
         // TODO: make this into a black background
@@ -597,45 +732,43 @@ impl Gui {
     }
 
     pub fn draw_cheat_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
-
         if ui.button("Free Clips").clicked() {
-            pc.cheat_clips();
+            self.actions.push(Action::Cheat(Cheat::Clips));
         }
         if ui.button("Free Money").clicked() {
-            pc.cheat_money();
+            self.actions.push(Action::Cheat(Cheat::Money));
         }
         if ui.button("Free Trust").clicked() {
-            pc.cheat_trust();
+            self.actions.push(Action::Cheat(Cheat::Trust));
         }
         if ui.button("Free Ops").clicked() {
-            pc.cheat_ops();
+            self.actions.push(Action::Cheat(Cheat::Ops));
         }
         if ui.button("Free Creativity").clicked() {
-            pc.cheat_creat();
+            self.actions.push(Action::Cheat(Cheat::Creativity));
         }
         if ui.button("Free Yomi").clicked() {
-            pc.cheat_yomi();
+            self.actions.push(Action::Cheat(Cheat::Yomi));
         }
         if ui.button("Reset Prestige").clicked() {
-            pc.reset_prestige();
+            self.actions.push(Action::Cheat(Cheat::ResetPrestige));
         }
 
         if ui.button("Destroy all Humans").clicked() {
-            pc.space.hypno_drone_event = Some(Instant::now());
+            self.actions.push(Action::Cheat(Cheat::DestroyAllHumans));
         }
         if ui.button("Free Prestige U").clicked() {
-            pc.cheat_prestige_u();
+            self.actions.push(Action::Cheat(Cheat::PrestigeU));
         }
         if ui.button("Free Prestige S").clicked() {
-            pc.cheat_prestige_s();
+            self.actions.push(Action::Cheat(Cheat::PrestigeS));
         }
         if ui.button("Set Battle Number 1 to 7").clicked() {
             // TODO:
             // self.set_b()
         }
         if ui.button("Set Avail Matter to 0").clicked() {
-            pc.zero_matter();
+            self.actions.push(Action::Cheat(Cheat::ZeroMatter));
         }
 
         // cheats that aren't in the original code
@@ -643,7 +776,7 @@ impl Gui {
             self.play_threnody();
         }
         if ui.button("Reset").clicked() {
-            self.paperclips.reset();
+            self.actions.push(Action::Reset);
         }
     }
 }
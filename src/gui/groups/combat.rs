@@ -1,11 +1,26 @@
-use eframe::egui::{Align2, Color32, CornerRadius, FontId, Pos2, Rect, Sense, Stroke, StrokeKind, Ui, Vec2};
-use paperclips::combat::{BATTLE_HEIGHT, BATTLE_WIDTH, ship::Team};
+use std::time::Duration;
+
+use eframe::egui::{Align2, Color32, CornerRadius, FontId, Pos2, Rect, Sense, Stroke, Ui, Vec2};
+use paperclips::{
+    combat::{BATTLE_HEIGHT, BATTLE_WIDTH, DEATH_FRAMES, ship::{Status, Team}},
+    project::PROJECT_121,
+    util::ticks_10ms,
+};
 
 use crate::gui::Gui;
 
+const BACKGROUND: Color32 = Color32::from_gray(20);
+const PROBE: Color32 = Color32::WHITE;
+const DRIFTER: Color32 = Color32::from_rgb(230, 70, 60);
+const HONOR_GAIN: Color32 = Color32::from_rgb(120, 220, 120);
+const HONOR_LOSS: Color32 = Color32::from_rgb(230, 90, 90);
+/// How long the honor of the last battle stays on the field
+const HONOR_SHOWN: Duration = Duration::from_secs(3);
+
 impl Gui {
     pub fn draw_combat_group(&mut self, ui: &mut Ui) {
-        let pc = &mut self.paperclips;
+        let pc = &self.paperclips;
+        let combat = &pc.combat;
 
         let size = ui.available_size();
         let width = size.x;
@@ -14,32 +29,70 @@ impl Gui {
 
         let (resp, painter) = ui.allocate_painter(Vec2::new(width, height), Sense::HOVER);
         let rect = resp.rect;
+        let painter = painter.with_clip_rect(rect);
+        let to_screen = |x: f64, y: f64| Pos2::new(
+            rect.left() + x as f32 / BATTLE_WIDTH as f32 * width,
+            rect.top() + y as f32 / BATTLE_HEIGHT as f32 * height,
+        );
 
-        for ship in &pc.combat.ships {
-            let x = rect.left() + ship.x as f32 / BATTLE_WIDTH as f32 * width;
-            let y = rect.top() + ship.y as f32 / BATTLE_HEIGHT as f32 * height;
-            let ship_rect = Rect::from_center_size(
-                (x, y).into(),
-                (unit, unit).into(),
-            );
-            painter.rect(
-                ship_rect,
-                CornerRadius::same(1),
-                match ship.team {
-                    Team::Left => Color32::WHITE,
-                    Team::Right => Color32::BLACK,
-                },
-                Stroke::NONE,
-                StrokeKind::Middle,
-            );
+        painter.rect_filled(rect, CornerRadius::ZERO, BACKGROUND);
+
+        if combat.battles {
+            for ship in &combat.ships {
+                let color = match ship.team {
+                    Team::Left => PROBE,
+                    Team::Right => DRIFTER,
+                };
+                let center = to_screen(ship.x, ship.y);
+                match ship.status {
+                    Status::Alive => {
+                        painter.rect_filled(Rect::from_center_size(center, Vec2::splat(unit * 2.0)), CornerRadius::same(1), color);
+                    },
+                    // a ring that grows while it fades out
+                    Status::Dead(frames) => {
+                        let t = frames as f32 / DEATH_FRAMES as f32;
+                        painter.circle_stroke(center, unit * (1.0 + 3.0 * t), Stroke::new(unit * 0.5, color.gamma_multiply(1.0 - t)));
+                    },
+                }
+            }
         }
 
-        painter.rect_filled(rect, CornerRadius::ZERO, Color32::DARK_GRAY);
-        painter.text(
-            Pos2::new(rect.left() + 10.0, rect.top() + 10.0),
-            Align2::LEFT_TOP, "no way!",
-            FontId::monospace(8.0),
-            Color32::BLACK,
-        );
+        let font = FontId::monospace(10.0);
+        let margin = 6.0;
+        let text = |pos: Pos2, align: Align2, text: String, color: Color32| {
+            painter.text(pos, align, text, font.clone(), color);
+        };
+
+        let title = match combat.battles {
+            true => combat.battle_name.to_string(),
+            false => "Awaiting the next battle".to_string(),
+        };
+        text(rect.left_top() + Vec2::splat(margin), Align2::LEFT_TOP, title, Color32::LIGHT_GRAY);
+        if combat.battles {
+            text(rect.left_bottom() + Vec2::new(margin, -margin), Align2::LEFT_BOTTOM, format!("Probes: {}", combat.ship_count.0), PROBE);
+            text(rect.right_bottom() + Vec2::new(-margin, -margin), Align2::RIGHT_BOTTOM, format!("Drifters: {}", combat.ship_count.1), DRIFTER);
+        }
+
+        // honor only counts once the battles are named
+        if pc.projects.is_active(PROJECT_121) {
+            let honor_pos = rect.right_top() + Vec2::new(-margin, margin);
+            text(honor_pos, Align2::RIGHT_TOP, format!("Honor: {}", combat.honor), Color32::LIGHT_GRAY);
+
+            // from the moment a side got wiped out until a while after the battle ended
+            let last = combat.history.last().filter(|record| {
+                record.honor_change != 0 && (record.winner.is_none() || pc.ticks.saturating_sub(record.ended()) < ticks_10ms(HONOR_SHOWN))
+            });
+            if let Some(record) = last {
+                let (change, color) = match record.honor_change {
+                    change if change > 0 => (format!("+{change}"), HONOR_GAIN),
+                    change => (change.to_string(), HONOR_LOSS),
+                };
+                text(honor_pos + Vec2::new(0.0, 14.0), Align2::RIGHT_TOP, change, color);
+            }
+            // the battle the threnody is for
+            if !combat.battles {
+                text(rect.left_bottom() + Vec2::new(margin, -margin), Align2::LEFT_BOTTOM, format!("Last defeat: {}", combat.threnody_title), Color32::GRAY);
+            }
+        }
     }
 }
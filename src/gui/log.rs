@@ -0,0 +1,74 @@
+use eframe::egui::{RichText, ScrollArea, TextEdit, TextStyle, Ui, Window};
+use paperclips::console::{LogFormat, format_game_time};
+
+use crate::gui::Gui;
+
+/// The window with the whole history of the console
+#[derive(Debug, Default)]
+pub struct ConsoleLog {
+    open: bool,
+    query: String,
+}
+
+impl ConsoleLog {
+    #[inline]
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+}
+
+impl Gui {
+    pub fn export_console_log(&mut self, format: LogFormat) {
+        let status = match self.saves.save_console_log(&self.paperclips.console, format) {
+            Ok(path) => format!("Exported the console log to {}", path.display()),
+            Err(e) => format!("Couldn't export the console log: {e}"),
+        };
+        self.saves.set_status(status);
+    }
+
+    pub fn draw_console_log(&mut self, ui: &mut Ui) {
+        let mut open = self.console_log.open;
+        let mut export = None;
+
+        Window::new("Console log")
+            .open(&mut open)
+            .default_size([500.0, 400.0])
+            .show(ui.ctx(), |ui| {
+                let console = &self.paperclips.console;
+
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.add(TextEdit::singleline(&mut self.console_log.query).desired_width(200.0));
+                    if ui.button("Export .txt").clicked() {
+                        export = Some(LogFormat::Text);
+                    }
+                    if ui.button("Export .json").clicked() {
+                        export = Some(LogFormat::Json);
+                    }
+                });
+
+                let entries: Vec<_> = console.search(&self.console_log.query).collect();
+                ui.weak(format!("{} of {} messages", entries.len(), console.history.len()));
+                ui.separator();
+
+                let row_height = ui.text_style_height(&TextStyle::Monospace);
+                ScrollArea::vertical()
+                    .auto_shrink(false)
+                    .stick_to_bottom(true)
+                    .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                        for entry in &entries[rows] {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(format_game_time(entry.elapsed())).monospace().weak())
+                                    .on_hover_text(format!("Tick {}", entry.ticks));
+                                ui.label(RichText::new(&*entry.message).monospace());
+                            });
+                        }
+                    });
+            });
+
+        if let Some(format) = export {
+            self.export_console_log(format);
+        }
+        self.console_log.open = open;
+    }
+}
@@ -1,48 +1,163 @@
-use std::time::{Duration, Instant};
+use std::{collections::VecDeque, sync::Arc, time::{Duration, Instant}};
 
 use eframe::{
-    App, Frame, egui::{CentralPanel, Panel, ScrollArea, Ui}
+    App, Frame, egui::{CentralPanel, Context, DragValue, MenuBar, Panel, ScrollArea, Ui}
 };
 use kittyaudio::Mixer;
-use paperclips::{PaperClips, util::number_cruncher};
+use paperclips::{PaperClips, Ticks, action::{Action, ActionError}, combat::MAX_SHIP_CAP, event::{GameEvent, MAX_EVENTS}, simulation::{Command, Simulation}, telemetry::TelemetryFormat, util::{number_cruncher, ticks_10ms}, warp::Speed};
+use strum::IntoEnumIterator;
 
-const TEN_MS: Duration = Duration::from_millis(10);
-const FRAME_60FPS: Duration = Duration::from_millis(16);
+use crate::gui::{battles::BattleLog, charts::Charts, log::ConsoleLog, saves::Saves};
+
+pub const APP_ID: &str = "paperclips";
+
+const ACTION_ERROR_TIME: Duration = Duration::from_secs(3);
+/// How long (in game time) the latest event stays in the menu bar
+const EVENT_TIME: Duration = Duration::from_secs(3);
 
 pub mod groups;
 pub mod blink;
 pub mod threnody;
 pub mod secrets;
+pub mod saves;
+pub mod log;
+pub mod charts;
+pub mod battles;
 
 pub struct Gui {
-    pub paperclips: PaperClips,
+    /// The latest snapshot of the game, only the simulation thread changes it
+    pub paperclips: Arc<PaperClips>,
+    /// Plays the game, records the replay and the telemetry
+    pub simulation: Simulation,
+    /// The latest events of the simulation, the oldest ones get dropped after `MAX_EVENTS`
+    pub events: VecDeque<(Ticks, GameEvent)>,
 
-    audio_mixer: Mixer,
+    /// Actions taken while drawing, sent to the simulation at the end of the frame
+    pub actions: Vec<Action>,
+    /// If the reference bots are playing, their actions are recorded like the player's
+    pub autopilot: bool,
+    pub speed: Speed,
+    /// Catch up on the time since a loaded save was made
+    pub offline_progress: bool,
 
-    last_main_update: Instant,
-    last_combat_update: Instant,
+    audio_mixer: Mixer,
+    saves: Saves,
+    console_log: ConsoleLog,
+    charts: Charts,
+    battle_log: BattleLog,
+    threnody_plays: u32,
+    /// Why the last action failed, shown for `ACTION_ERROR_TIME`
+    action_error: Option<(Instant, ActionError)>,
 }
 
-impl Default for Gui {
-    fn default() -> Self {
+impl Gui {
+    /// The window gets repainted whenever the simulation publishes a snapshot
+    pub fn new(ctx: Context) -> Self {
+        let paperclips = PaperClips::default();
         Self {
-            paperclips: PaperClips::default(),
+            simulation: Simulation::spawn(paperclips.clone(), move || ctx.request_repaint()),
+            paperclips: Arc::new(paperclips),
+            events: VecDeque::new(),
+            actions: Vec::new(),
+            autopilot: false,
+            speed: Speed::default(),
+            offline_progress: true,
 
             audio_mixer: {
                 let mixer = Mixer::new();
                 mixer.init();
                 mixer
             },
-
-            last_main_update: Instant::now(),
-            last_combat_update: Instant::now(),
+            saves: Saves::default(),
+            console_log: ConsoleLog::default(),
+            charts: Charts::default(),
+            battle_log: BattleLog::default(),
+            threnody_plays: 0,
+            action_error: None,
         }
     }
 }
 
 impl App for Gui {
     fn ui(&mut self, ui: &mut Ui, _frame: &mut Frame) {
-        self.update_paperclips(ui);
+        self.update_paperclips();
+        self.autosave();
+
+        Panel::top("menu").show_inside(ui, |ui| {
+            MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("Game", |ui| {
+                    if ui.button("Save now").clicked() {
+                        self.save_game();
+                    }
+                    if ui.button("Load game...").clicked() {
+                        self.saves.open_picker();
+                    }
+                    if ui.button("Import from web version...").clicked() {
+                        self.saves.open_import();
+                    }
+                    ui.checkbox(&mut self.offline_progress, "Offline progress")
+                        .on_hover_text("Catch up on the time since a save was made when loading it");
+                    let mut ship_cap = self.paperclips.combat.ship_cap;
+                    let response = ui.add(DragValue::new(&mut ship_cap).range(1..=MAX_SHIP_CAP).speed(10).prefix("Ships per side: "))
+                        .on_hover_text("The most ships a side can get in a battle, 200 in the original. Honor stays the same as with 200");
+                    if response.changed() {
+                        self.actions.push(Action::SetShipCap(ship_cap));
+                    }
+                    ui.separator();
+                    if ui.button("Save replay").clicked() {
+                        self.save_replay();
+                    }
+                    if ui.button("Export telemetry (.csv)").clicked() {
+                        self.export_telemetry(TelemetryFormat::Csv);
+                    }
+                    if ui.button("Export telemetry (.json)").clicked() {
+                        self.export_telemetry(TelemetryFormat::Json);
+                    }
+                });
+                if ui.toggle_value(&mut self.autopilot, "Autopilot").on_hover_text("Let the reference bots play").changed() {
+                    self.simulation.send(Command::SetAutopilot(self.autopilot));
+                }
+                ui.menu_button(format!("Speed: {}", self.speed), |ui| {
+                    for speed in Speed::iter() {
+                        if ui.selectable_label(self.speed == speed, speed.to_string()).clicked() {
+                            self.speed = speed;
+                            self.simulation.send(Command::SetSpeed(speed));
+                        }
+                    }
+                });
+                ui.menu_button("View", |ui| {
+                    if ui.button("Console log").clicked() {
+                        self.console_log.open();
+                    }
+                    if ui.button("Statistics").clicked() {
+                        self.charts.open();
+                    }
+                    if ui.button("Battle history").clicked() {
+                        self.battle_log.open();
+                    }
+                });
+                if let Some(status) = self.saves.status() {
+                    ui.weak(status);
+                }
+                if let Some((since, e)) = self.action_error {
+                    match since.elapsed() < ACTION_ERROR_TIME {
+                        true => { ui.colored_label(ui.visuals().warn_fg_color, e.to_string()); },
+                        false => self.action_error = None,
+                    }
+                }
+                if self.action_error.is_none()
+                    && let Some(&(ticks, event)) = self.events.back()
+                    && self.paperclips.ticks.saturating_sub(ticks) < ticks_10ms(EVENT_TIME)
+                {
+                    ui.weak(event.to_string());
+                }
+            });
+        });
+        self.draw_load_picker(ui);
+        self.draw_import_window(ui);
+        self.draw_console_log(ui);
+        self.draw_charts(ui);
+        self.draw_battle_log(ui);
 
         Panel::top("console").show_inside(ui, |ui| {
             // #consoleDiv
@@ -63,11 +178,14 @@ impl App for Gui {
                     // LEFT COLUMN
                     self.draw_make_paperclip(left);
 
-                    match self.paperclips.human_flag {
+                    match self.paperclips.human_flag() {
                         false => {
                             self.draw_creation_group(left);
                             self.draw_wire_production_group(left);
-                            if self.paperclips.space.space_flag {
+                            if self.paperclips.power_grid_flag() {
+                                self.draw_power_group(left);
+                            }
+                            if self.paperclips.space_flag() {
                                 self.draw_space_group(left);
                             }
                         },
@@ -93,52 +211,65 @@ impl App for Gui {
                     }
 
                     // RIGHT COLUMN
+                    if self.paperclips.space_flag() {
+                        self.draw_probe_design_group(right);
+                    }
                     if self.paperclips.investments.engine_flag {
                         self.draw_investments_group(right);
                     }
                     if self.paperclips.strategy.engine_flag {
                         self.draw_strategy_group(right);
                     }
-                    self.draw_combat_group(right); // TODO
+                    if self.paperclips.combat.battle_flag {
+                        self.draw_combat_group(right);
+                    }
                 });
             });
         });
+
+        self.apply_actions();
+    }
+    fn on_exit(&mut self) {
+        self.save_game();
     }
 }
 
 impl Gui {
-    pub fn update_paperclips(&mut self, ui: &mut Ui) {
-        macro_rules! update_time {
-            ($($prop:ident($time:expr) $code:block)*) => {
-                const TOTAL_LOOPS: usize = [$(stringify!($prop)),*].len();
-                $(
-                    let start = Instant::now();
-                    while start.elapsed() < FRAME_60FPS / TOTAL_LOOPS as u32 {
-                        if self.$prop.elapsed() >= $time {
-                            self.$prop += $time;
-                            $code;
-                        } else {
-                            break
-                        }
-                    }
-                )*
-            };
+    /// Picks up the latest snapshot of the simulation and what happened to the actions
+    pub fn update_paperclips(&mut self) {
+        self.paperclips = self.simulation.snapshot();
+        if let Some(e) = self.simulation.errors().last() {
+            self.action_error = Some((Instant::now(), e));
         }
-        update_time!{
-            last_main_update(TEN_MS) {
-                self.paperclips.main_tick();
-                self.check_threnody();
-            }
-            last_combat_update(FRAME_60FPS) {
-                self.paperclips.update_combat();
+        for event in self.simulation.events() {
+            if self.events.len() >= MAX_EVENTS {
+                self.events.pop_front();
             }
+            self.events.push_back(event);
         }
-
-        ui.request_repaint_after(TEN_MS.saturating_sub(self.last_main_update.elapsed()));
+        self.check_threnody();
+    }
+    /// Sends the actions taken during this frame to the simulation
+    pub fn apply_actions(&mut self) {
+        for action in self.actions.drain(..) {
+            self.simulation.act(action);
+        }
+    }
+    /// Replaces the current game, e.g. after loading a save
+    pub fn start_game(&mut self, paperclips: PaperClips) {
+        self.threnody_plays = paperclips.threnody.plays;
+        self.events.clear();
+        self.simulation.start(paperclips);
+        self.paperclips = self.simulation.snapshot();
     }
     pub fn check_threnody(&mut self) {
-        if self.paperclips.threnody.check() {
-            self.play_threnody();
+        let plays = self.paperclips.threnody.plays;
+        if plays != self.threnody_plays {
+            let played = plays > self.threnody_plays;
+            self.threnody_plays = plays;
+            if played {
+                self.play_threnody();
+            }
         }
     }
 }
@@ -0,0 +1,255 @@
+use std::{fs, path::PathBuf, time::{Duration, Instant, SystemTime}};
+
+use eframe::egui::{Grid, ScrollArea, TextEdit, Ui, Window};
+use paperclips::{Float, PaperClips, Ticks, console::{Console, LogFormat}, progression::Progression, replay::Replay, save::{SaveError, SaveFile}, telemetry::{Telemetry, TelemetryFormat}, util::{number_cruncher, ticks_10ms, ticks_to_duration, time_cruncher}, warp::OFFLINE_BUDGET};
+
+use crate::gui::{APP_ID, Gui};
+
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+pub const AUTOSAVE_SLOTS: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct SlotInfo {
+    pub slot: usize,
+    pub modified: SystemTime,
+    pub ticks: Ticks,
+    pub clips: Float,
+    pub progression: Progression,
+}
+
+/// Rotating save slots, the oldest one gets overwritten first
+pub struct Saves {
+    dir: Option<PathBuf>,
+    next_slot: usize,
+    last_save: Instant,
+    picker_open: bool,
+    slots: Vec<SlotInfo>,
+    status: Option<String>,
+    /// `Some` while the import window is open, holds the pasted localStorage
+    import: Option<String>,
+}
+
+impl Default for Saves {
+    fn default() -> Self {
+        let mut saves = Self {
+            dir: eframe::storage_dir(APP_ID).map(|dir| dir.join("saves")),
+            next_slot: 0,
+            last_save: Instant::now(),
+            picker_open: false,
+            slots: Vec::new(),
+            status: None,
+            import: None,
+        };
+        saves.refresh();
+        saves.next_slot = saves.oldest_slot();
+        saves
+    }
+}
+
+impl Saves {
+    fn slot_path(&self, slot: usize) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("slot{slot}.ron")))
+    }
+    /// Returns the first empty slot, or the one that was saved the longest time ago
+    fn oldest_slot(&self) -> usize {
+        (0..AUTOSAVE_SLOTS)
+            .min_by_key(|&slot| self.slots.iter().find(|info| info.slot == slot).map(|info| info.modified))
+            .unwrap_or(0)
+    }
+    /// Reads every slot from disk, newest first
+    pub fn refresh(&mut self) {
+        self.slots = (0..AUTOSAVE_SLOTS)
+            .filter_map(|slot| {
+                let path = self.slot_path(slot)?;
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                let pc = PaperClips::load_from_file(&path).ok()?;
+                Some(SlotInfo {
+                    slot,
+                    modified,
+                    ticks: pc.ticks,
+                    clips: pc.business.clips,
+                    progression: pc.progression,
+                })
+            })
+            .collect();
+        self.slots.sort_by_key(|info| std::cmp::Reverse(info.modified));
+    }
+    pub fn save(&mut self, pc: &PaperClips) -> Result<usize, SaveError> {
+        let slot = self.next_slot;
+        let path = self.slot_path(slot).ok_or_else(|| std::io::Error::other("no data directory available"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        pc.save_to_file(path)?;
+        self.next_slot = (slot + 1) % AUTOSAVE_SLOTS;
+        Ok(slot)
+    }
+    /// Replays are kept apart from the slots and never overwritten
+    pub fn save_replay(&self, replay: &Replay) -> Result<PathBuf, SaveError> {
+        let dir = self.dir.as_ref().ok_or_else(|| std::io::Error::other("no data directory available"))?.join("replays");
+        fs::create_dir_all(&dir)?;
+        let since_epoch = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        let path = dir.join(format!("replay_{}.ron", since_epoch.as_secs()));
+        replay.save_to_file(&path)?;
+        Ok(path)
+    }
+    /// Console logs go next to the replays
+    pub fn save_console_log(&self, console: &Console, format: LogFormat) -> Result<PathBuf, SaveError> {
+        let dir = self.dir.as_ref().ok_or_else(|| std::io::Error::other("no data directory available"))?.join("logs");
+        fs::create_dir_all(&dir)?;
+        let since_epoch = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        let path = dir.join(format!("console_{}.{}", since_epoch.as_secs(), format.extension()));
+        console.export_history(&path)?;
+        Ok(path)
+    }
+    pub fn save_telemetry(&self, telemetry: &Telemetry, format: TelemetryFormat) -> Result<PathBuf, SaveError> {
+        let dir = self.dir.as_ref().ok_or_else(|| std::io::Error::other("no data directory available"))?.join("telemetry");
+        fs::create_dir_all(&dir)?;
+        let since_epoch = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        let path = dir.join(format!("telemetry_{}.{}", since_epoch.as_secs(), format.extension()));
+        telemetry.export(&path)?;
+        Ok(path)
+    }
+    pub fn load(&self, slot: usize) -> Result<SaveFile, SaveError> {
+        let path = self.slot_path(slot).ok_or_else(|| std::io::Error::other("no data directory available"))?;
+        SaveFile::load_from_file(path)
+    }
+    pub fn open_picker(&mut self) {
+        self.refresh();
+        self.picker_open = true;
+    }
+    pub fn open_import(&mut self) {
+        self.import = Some(String::new());
+    }
+    #[inline]
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+    #[inline]
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = Some(status.into());
+    }
+}
+
+impl Gui {
+    /// Saves the game once every `AUTOSAVE_INTERVAL`
+    pub fn autosave(&mut self) {
+        if self.saves.last_save.elapsed() >= AUTOSAVE_INTERVAL {
+            self.save_game();
+        }
+    }
+    pub fn save_game(&mut self) {
+        self.saves.status = Some(match self.saves.save(&self.paperclips) {
+            Ok(slot) => format!("Saved to slot {}", slot + 1),
+            Err(e) => format!("Couldn't save: {e}"),
+        });
+        self.saves.last_save = Instant::now();
+    }
+    pub fn save_replay(&mut self) {
+        let Some(replay) = self.simulation.replay() else {
+            return self.saves.set_status("Couldn't save the replay: the simulation stopped")
+        };
+        self.saves.status = Some(match self.saves.save_replay(&replay) {
+            Ok(path) => format!("Saved replay to {}", path.display()),
+            Err(e) => format!("Couldn't save the replay: {e}"),
+        });
+    }
+    pub fn export_telemetry(&mut self, format: TelemetryFormat) {
+        let Some(telemetry) = self.simulation.telemetry() else {
+            return self.saves.set_status("Couldn't export the telemetry: the simulation stopped")
+        };
+        self.saves.status = Some(match self.saves.save_telemetry(&telemetry, format) {
+            Ok(path) => format!("Exported {} rows to {}", telemetry.rows.len(), path.display()),
+            Err(e) => format!("Couldn't export the telemetry: {e}"),
+        });
+    }
+    pub fn load_game(&mut self, slot: usize) {
+        match self.saves.load(slot) {
+            Ok(save) => {
+                let away = save.time_away().filter(|_| self.offline_progress);
+                let mut pc = save.paperclips;
+                self.saves.status = Some(match away {
+                    Some(away) => {
+                        let catch_up = pc.catch_up(ticks_10ms(away), OFFLINE_BUDGET);
+                        format!("Loaded slot {} after {:.0?} away: {catch_up}", slot + 1, away)
+                    },
+                    None => format!("Loaded slot {}", slot + 1),
+                });
+                self.start_game(pc);
+            }
+            Err(e) => self.saves.status = Some(format!("Couldn't load slot {}: {e}", slot + 1)),
+        }
+    }
+
+    pub fn draw_load_picker(&mut self, ui: &mut Ui) {
+        let mut open = self.saves.picker_open;
+        let mut to_load = None;
+
+        Window::new("Load game")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ui.ctx(), |ui| {
+                if self.saves.slots.is_empty() {
+                    ui.label("No saves yet");
+                    return
+                }
+                Grid::new("save_slots").striped(true).show(ui, |ui| {
+                    for heading in ["Slot", "Saved", "Played", "Clips", "Stage", ""] {
+                        ui.strong(heading);
+                    }
+                    ui.end_row();
+
+                    for info in &self.saves.slots {
+                        let age = SystemTime::now().duration_since(info.modified).unwrap_or_default();
+                        ui.label((info.slot + 1).to_string());
+                        ui.label(format!("{}ago", time_cruncher(age)));
+                        ui.label(time_cruncher(ticks_to_duration(info.ticks)));
+                        ui.label(number_cruncher(info.clips, Some(1)));
+                        ui.label(info.progression.to_string());
+                        if ui.button("Load").clicked() {
+                            to_load = Some(info.slot);
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+        if let Some(slot) = to_load {
+            self.load_game(slot);
+            open = false;
+        }
+        self.saves.picker_open = open;
+    }
+
+    /// Takes the save of the original game, as printed by `JSON.stringify(localStorage)`
+    pub fn draw_import_window(&mut self, ui: &mut Ui) {
+        let Some(blob) = &mut self.saves.import else { return };
+        let mut open = true;
+        let mut import = false;
+
+        Window::new("Import from the web version")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ui.ctx(), |ui| {
+                ui.label("Run `copy(JSON.stringify(localStorage))` in the browser console of the original game and paste the result here");
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    ui.add(TextEdit::multiline(blob).code_editor().desired_width(f32::INFINITY));
+                });
+                import = ui.button("Import").clicked();
+            });
+
+        if import {
+            match PaperClips::from_js_save(blob) {
+                Ok(pc) => {
+                    self.start_game(pc);
+                    self.saves.status = Some("Imported the web version save".to_string());
+                    open = false;
+                }
+                Err(e) => self.saves.status = Some(format!("Couldn't import: {e}")),
+            }
+        }
+        if !open {
+            self.saves.import = None;
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use eframe::{run_native, NativeOptions};
 
-use crate::{gui::Gui, setup::init_setup};
+use crate::{gui::{APP_ID, Gui}, setup::init_setup};
 
 pub mod gui;
 pub mod setup;
@@ -11,12 +11,12 @@ fn main() {
     init_setup();
 
     run_native(
-        "paperclips",
+        APP_ID,
         NativeOptions {
             ..Default::default()
         },
-        Box::new(|_cc| {
-            let gui = Gui::default();
+        Box::new(|cc| {
+            let gui = Gui::new(cc.egui_ctx.clone());
             Ok(Box::new(gui))
         })
     ).unwrap();
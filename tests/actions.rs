@@ -0,0 +1,191 @@
+use paperclips::{
+    PaperClips,
+    action::{Action, ActionError, Cheat, Resource},
+    progression::Progression,
+    factory::{FACTORY_COST, battery_price, drone_price, farm_price},
+    project::{PROJECT_1, PROJECT_127, ProjectStatus},
+    space::{PROBE_COST, ProbeStat},
+};
+
+#[test]
+fn failed_actions_change_nothing() {
+    let mut pc = PaperClips::default();
+    pc.wire.count = 0.0;
+    let before = pc.to_save_string().unwrap();
+
+    assert_eq!(
+        pc.apply(Action::MakePaperclip),
+        Err(ActionError::NotEnough { resource: Resource::Wire, needed: 1.0, available: 0.0 }),
+    );
+    assert!(matches!(pc.apply(Action::BuyWire), Err(ActionError::NotEnough { resource: Resource::Funds, .. })));
+    assert_eq!(pc.apply(Action::MakeClipper), Err(ActionError::Locked));
+    assert_eq!(pc.apply(Action::InvestDeposit), Err(ActionError::Locked));
+    assert_eq!(pc.apply(Action::BuyProject(&PROJECT_1)), Err(ActionError::ProjectNotBuyable));
+    assert_eq!(pc.to_save_string().unwrap(), before);
+}
+
+#[test]
+fn successful_actions() {
+    let mut pc = PaperClips::default();
+    let wire = pc.wire.count;
+    assert_eq!(pc.apply(Action::MakePaperclip), Ok(()));
+    assert_eq!(pc.business.clips, 1.0);
+    assert_eq!(pc.wire.count, wire - 1.0);
+
+    pc.apply(Action::Cheat(Cheat::Money)).unwrap();
+    pc.apply(Action::BuyWire).unwrap();
+    assert!(pc.wire.count > wire);
+
+    pc.business.margin = 0.01;
+    assert_eq!(pc.apply(Action::LowerPrice), Err(ActionError::MinimumPrice));
+    assert_eq!(pc.apply(Action::RaisePrice), Ok(()));
+}
+
+#[test]
+fn trust_limits_processors_and_memory() {
+    let mut pc = PaperClips::default();
+    pc.computational.comp_flag = true;
+    pc.computational.trust = 2;
+    pc.computational.processors = 1;
+    pc.computational.memory = 1;
+
+    assert!(matches!(pc.apply(Action::AddProcessor), Err(ActionError::NotEnough { resource: Resource::Trust, .. })));
+    pc.computational.trust = 3;
+    assert_eq!(pc.apply(Action::AddMemory), Ok(()));
+    assert_eq!(pc.computational.memory, 2);
+    assert!(pc.apply(Action::AddProcessor).is_err());
+}
+
+#[test]
+fn swarm_gifts_pay_for_processors_after_the_humans() {
+    let mut pc = PaperClips { progression: Progression::PostHuman, ..Default::default() };
+    pc.computational.comp_flag = true;
+    pc.computational.trust = 100;
+    pc.factory.swarm_gifts = 1.5;
+
+    assert_eq!(pc.apply(Action::AddProcessor), Ok(()));
+    assert_eq!(pc.factory.swarm_gifts, 0.5);
+    // trust doesn't count anymore, and gifts can't go negative
+    assert!(matches!(pc.apply(Action::AddMemory), Err(ActionError::NotEnough { resource: Resource::SwarmGifts, .. })));
+    pc.add_memory();
+    assert_eq!(pc.factory.swarm_gifts, 0.5);
+}
+
+#[test]
+fn strats_can_only_be_picked_before_the_rounds() {
+    let mut pc = PaperClips::default();
+    pc.strategy.engine_flag = true;
+    pc.computational.standard_ops = pc.strategy.tourney_cost;
+    pc.computational.operations = pc.strategy.tourney_cost;
+    let strat = pc.strategy.strats[0].0;
+
+    assert_eq!(pc.apply(Action::PickStrat(strat)), Err(ActionError::NoTournament));
+    pc.apply(Action::NewTourney).unwrap();
+    assert_eq!(pc.apply(Action::PickStrat(strat)), Ok(()));
+    pc.apply(Action::RunTourney).unwrap();
+    assert_eq!(pc.apply(Action::PickStrat(strat)), Err(ActionError::TournamentInProgress));
+}
+
+#[test]
+fn post_human_purchases() {
+    let mut pc = PaperClips { progression: Progression::PostHuman, ..Default::default() };
+    pc.factory.factory_flag = true;
+    pc.factory.harvester_flag = true;
+    pc.business.unused_clips = 1e12;
+
+    assert_eq!(pc.apply(Action::MakeWireDrone(1)), Err(ActionError::Locked));
+
+    pc.apply(Action::MakeFactory).unwrap();
+    assert_eq!(pc.factory.factory_level, 1.0);
+    assert_eq!(pc.factory.factory_bill, FACTORY_COST);
+    assert_eq!(pc.factory.factory_cost, FACTORY_COST * 10.0);
+
+    let price = drone_price(0.0, 10);
+    assert_eq!(pc.factory.harvester_price(10), price);
+    let clips = pc.business.unused_clips;
+    pc.apply(Action::MakeHarvester(10)).unwrap();
+    assert_eq!(pc.factory.harvester_level, 10.0);
+    assert!((clips - pc.business.unused_clips - price).abs() < 1.0);
+    assert_eq!(pc.factory.harvester_cost, drone_price(10.0, 1));
+    assert_eq!(pc.factory.p10h, drone_price(10.0, 10));
+
+    assert_eq!(pc.apply(Action::MakeHarvester(u32::MAX)), Err(ActionError::UnsupportedAmount(u32::MAX)));
+    assert_eq!(pc.apply(Action::MakeHarvester(2)), Err(ActionError::UnsupportedAmount(2)));
+    assert_eq!(pc.factory.harvester_level, 10.0);
+
+    pc.business.unused_clips = 0.0;
+    assert!(matches!(pc.apply(Action::MakeHarvester(1)), Err(ActionError::NotEnough { resource: Resource::Clips, .. })));
+
+    assert_eq!(pc.apply(Action::MakeProbe), Err(ActionError::Locked));
+    assert!(pc.advance(Progression::Space));
+    pc.business.unused_clips = PROBE_COST;
+    pc.apply(Action::MakeProbe).unwrap();
+    assert_eq!((pc.space.probe_count, pc.space.probe_launch_level), (1.0, 1.0));
+    assert_eq!(pc.apply(Action::MakeFactory), Err(ActionError::Locked));
+}
+
+#[test]
+fn probe_design_stays_within_trust() {
+    let mut pc = PaperClips { progression: Progression::Space, ..Default::default() };
+    pc.strategy.yomi = 1e6;
+
+    assert!(matches!(
+        pc.apply(Action::RaiseProbeStat(ProbeStat::Speed)),
+        Err(ActionError::NotEnough { resource: Resource::ProbeTrust, .. }),
+    ));
+    let cost = pc.space.probe_trust_cost;
+    pc.apply(Action::IncreaseProbeTrust).unwrap();
+    pc.apply(Action::IncreaseProbeTrust).unwrap();
+    assert_eq!(pc.space.probe_trust, 2.0);
+    assert!(pc.strategy.yomi < 1e6 - cost);
+
+    pc.apply(Action::RaiseProbeStat(ProbeStat::Rep)).unwrap();
+    pc.apply(Action::RaiseProbeStat(ProbeStat::Haz)).unwrap();
+    assert!(pc.apply(Action::RaiseProbeStat(ProbeStat::Rep)).is_err());
+    assert_eq!(pc.space.probe_used_trust, 2.0);
+
+    pc.apply(Action::LowerProbeStat(ProbeStat::Haz)).unwrap();
+    assert_eq!(pc.apply(Action::LowerProbeStat(ProbeStat::Haz)), Err(ActionError::ProbeStatAtZero));
+    assert_eq!((pc.space.probe_rep, pc.space.probe_haz, pc.space.probe_used_trust), (1.0, 0.0, 1.0));
+
+    pc.space.probe_trust = pc.space.max_trust;
+    assert_eq!(pc.apply(Action::IncreaseProbeTrust), Err(ActionError::MaxTrust));
+    assert_eq!(pc.apply(Action::IncreaseMaxTrust), Err(ActionError::Locked));
+}
+
+#[test]
+fn power_grid_purchases() {
+    let mut pc = PaperClips { progression: Progression::PostHuman, ..Default::default() };
+    pc.business.unused_clips = 1e15;
+
+    assert_eq!(pc.apply(Action::MakeFarm(1)), Err(ActionError::Locked));
+    *pc.projects.status_mut(PROJECT_127) = ProjectStatus::Bought;
+
+    let clips = pc.business.unused_clips;
+    let farm_cost = pc.factory.farm_cost;
+    pc.apply(Action::MakeFarm(1)).unwrap();
+    pc.apply(Action::MakeFarm(10)).unwrap();
+    assert_eq!(pc.factory.farm_level, 11);
+    let spent = clips - pc.business.unused_clips;
+    assert!((pc.factory.farm_bill - spent).abs() < 1.0);
+    assert!((pc.factory.farm_bill - (farm_cost + farm_price(1.0, 10))).abs() < 1.0);
+    assert_eq!(pc.factory.p10f, farm_price(11.0, 10));
+
+    pc.apply(Action::MakeBattery(100)).unwrap();
+    assert_eq!(pc.factory.battery_level, 100);
+    assert_eq!(pc.factory.battery_cost, battery_price(100.0, 1));
+
+    assert_eq!(pc.apply(Action::MakeFarm(1000)), Err(ActionError::UnsupportedAmount(1000)));
+    assert_eq!(pc.apply(Action::MakeBattery(u32::MAX)), Err(ActionError::UnsupportedAmount(u32::MAX)));
+    assert_eq!((pc.factory.farm_level, pc.factory.battery_level), (11, 100));
+
+    let bills = pc.factory.farm_bill + pc.factory.battery_bill;
+    let clips = pc.business.unused_clips;
+    pc.apply(Action::FarmReboot).unwrap();
+    pc.apply(Action::BatteryReboot).unwrap();
+    assert_eq!((pc.factory.farm_level, pc.factory.battery_level), (0, 0));
+    assert!((pc.business.unused_clips - (clips + bills)).abs() < 1.0);
+
+    pc.business.unused_clips = 0.0;
+    assert!(matches!(pc.apply(Action::MakeBattery(10)), Err(ActionError::NotEnough { resource: Resource::Clips, .. })));
+}
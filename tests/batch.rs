@@ -0,0 +1,47 @@
+use paperclips::{
+    PaperClips,
+    batch::{Batch, Distribution},
+    speedrun::Goal,
+};
+
+#[test]
+fn distributions_use_the_nearest_rank() {
+    let d = Distribution::new((1..=10).rev().map(|n| n as f64).collect()).unwrap();
+    assert_eq!((d.count, d.min, d.p10, d.median, d.p90, d.max), (10, 1.0, 1.0, 5.0, 9.0, 10.0));
+    assert_eq!(d.mean, 5.5);
+    assert!((d.std_dev - 8.25f64.sqrt()).abs() < 1e-12);
+    assert_eq!(Distribution::new(Vec::new()), None);
+}
+
+#[test]
+fn results_dont_depend_on_the_threads() {
+    let initial = PaperClips::default();
+    let batch = Batch { goal: Some(Goal::Milestone(1)), seed: 10, threads: 1, ..Batch::new(4, 8_000) };
+    let report = batch.run(&initial, |_| {});
+    assert_eq!(report.results.iter().map(|result| result.seed).collect::<Vec<_>>(), [10, 11, 12, 13]);
+    assert!(report.results.iter().all(|result| result.reached && result.milestones.len() >= 2));
+    assert_eq!(report.milestone(1).unwrap().count, 4);
+
+    let threaded = Batch { threads: 3, ..batch }.run(&initial, |_| {});
+    assert_eq!(threaded.results, report.results);
+    assert_eq!(batch.play(&initial, 2), report.results[2]);
+
+    let csv = report.to_csv();
+    assert_eq!(csv.lines().count(), 5);
+    assert!(csv.starts_with("seed,ticks,reached,"));
+}
+
+#[test]
+fn loaded_games_keep_the_milestone_ids() {
+    let mut initial = PaperClips { milestones: 1, ..Default::default() };
+    initial.business.clips = 600.0;
+    let report = Batch { bots: false, threads: 1, ..Batch::new(2, 10) }.run(&initial, |_| {});
+
+    assert!(report.results.iter().all(|result| result.milestones == [(1, 1)]));
+    assert_eq!(report.milestones(), [1]);
+    assert_eq!(report.milestone(1).unwrap().count, 2);
+    assert_eq!(report.milestone(0), None);
+    assert!(report.to_csv().lines().next().unwrap().ends_with(",honor,milestone_1"));
+    assert!(report.to_string().contains("milestone 1"));
+    assert!(!report.to_string().contains("milestone 0"));
+}
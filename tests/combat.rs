@@ -0,0 +1,174 @@
+use paperclips::{
+    PaperClips,
+    action::Action,
+    combat::{DEFAULT_SHIP_CAP, GRID_HEIGHT, GRID_WIDTH, Honor, ShipCount, history::{BattleRecord, WarSummary, Winner}, ship::{Status, Team}},
+    event::GameEvent,
+    project::{PROJECT_121, ProjectStatus},
+    rng::PCRng,
+};
+
+fn battle(ship_cap: ShipCount) -> PaperClips {
+    let mut pc = PaperClips { rng: PCRng::seeded(4), ..Default::default() };
+    pc.space.probe_count = 1e15;
+    pc.space.drifter_count = 1e15;
+    pc.combat.ship_cap = ship_cap;
+    pc.create_battle();
+    pc
+}
+
+fn alive(pc: &PaperClips, team: Team) -> ShipCount {
+    pc.combat.ships.iter().filter(|s| s.team == team && s.status == Status::Alive).count() as ShipCount
+}
+
+#[test]
+fn battles_can_go_past_255_ships() {
+    let pc = battle(1000);
+    assert!(pc.combat.max_ships.0 > 255 || pc.combat.max_ships.1 > 255, "{:?}", pc.combat.max_ships);
+    assert!(pc.combat.max_ships.0 <= 1000 && pc.combat.max_ships.1 <= 1000);
+    assert_eq!(pc.combat.ship_count, pc.combat.max_ships);
+}
+
+#[test]
+fn grid_has_every_alive_ship_once() {
+    let mut pc = battle(500);
+    for _ in 0..50 {
+        pc.update_combat();
+        pc.combat.update_grid();
+        let mut seen = vec![0; pc.combat.ships.len()];
+        for gx in 0..GRID_WIDTH {
+            for gy in 0..GRID_HEIGHT {
+                for &i in pc.combat.grid.cell(gx, gy) {
+                    let s = &pc.combat.ships[i as usize];
+                    assert_eq!((s.gx, s.gy), (gx, gy));
+                    seen[i as usize] += 1;
+                }
+            }
+        }
+        for (s, seen) in pc.combat.ships.iter().zip(seen) {
+            assert_eq!(seen, (s.status == Status::Alive) as u32);
+        }
+    }
+}
+
+#[test]
+fn killed_ships_die_and_get_removed() {
+    let mut pc = battle(300);
+    let ships = pc.combat.ships.len();
+    for _ in 0..200 {
+        pc.update_combat();
+        assert_eq!(pc.combat.ship_count, (alive(&pc, Team::Left), alive(&pc, Team::Right)));
+    }
+    assert!(pc.combat.ships.len() < ships);
+    assert!(pc.space.probes_lost_combat > 0.0 || pc.space.drifters_killed > 0.0);
+}
+
+/// Plays the battle until it ends, with the battles named so honor counts
+fn fight(pc: &mut PaperClips) -> (BattleRecord, Vec<GameEvent>) {
+    *pc.projects.status_mut(PROJECT_121) = ProjectStatus::Bought;
+    pc.combat.honor = 1000;
+    assert!(pc.combat.battles);
+    let mut frames = 0;
+    while pc.combat.battles {
+        pc.update_combat();
+        frames += 1;
+        assert!(frames <= 8000, "the battle never ended");
+    }
+    let [record] = pc.combat.history[..] else { panic!("{} battles recorded", pc.combat.history.len()) };
+    assert_eq!(record.max_ships, pc.combat.max_ships);
+    assert_eq!(record.duration as usize, frames);
+    assert_eq!(record.probes_lost, pc.space.probes_lost_combat);
+    assert_eq!(record.drifters_killed, pc.space.drifters_killed);
+    let events = pc.take_events().into_iter().map(|(_, event)| event).collect();
+    (record, events)
+}
+
+#[test]
+fn won_battles_give_honor() {
+    let mut pc = battle(200);
+    // the drifters can't hurt the probes anymore
+    pc.space.drifter_count = 0.0;
+    let (record, events) = fight(&mut pc);
+
+    let honor = pc.combat.max_ships.1 as Honor;
+    assert_eq!(pc.combat.ship_count.1, 0);
+    assert!(matches!(events[..], [GameEvent::BattleWon { honor: won }] if won == honor), "{events:?}");
+    assert_eq!(pc.combat.honor, 1000 + honor);
+    assert_eq!((record.winner, record.honor_change), (Some(Winner::Probes), honor as i64));
+}
+
+#[test]
+fn lost_battles_take_honor() {
+    let mut pc = battle(200);
+    // the probes can't hurt the drifters anymore
+    pc.space.probe_count = 0.0;
+    let (record, events) = fight(&mut pc);
+
+    let honor = pc.combat.max_ships.0 as Honor;
+    assert_eq!(pc.combat.ship_count.0, 0);
+    assert!(matches!(events[..], [GameEvent::BattleLost { honor: lost }] if lost == honor), "{events:?}");
+    assert_eq!(pc.combat.honor, 1000 - honor);
+    assert_eq!((record.winner, record.honor_change), (Some(Winner::Drifters), -(honor as i64)));
+}
+
+#[test]
+fn honor_doesnt_depend_on_the_ship_cap() {
+    for (probes, drifters) in [(3e7, 4.2e7), (1e15, 1e15)] {
+        let fleets = |ship_cap| {
+            let mut pc = PaperClips { rng: PCRng::seeded(4), ..Default::default() };
+            pc.space.probe_count = probes;
+            pc.space.drifter_count = drifters;
+            pc.combat.ship_cap = ship_cap;
+            pc.create_battle();
+            pc.combat
+        };
+        // the original cap draws as many ships as they're worth
+        let original = fleets(DEFAULT_SHIP_CAP);
+        assert_eq!(original.battle_honor, (original.max_ships.0 as Honor, original.max_ships.1 as Honor));
+        for ship_cap in [1, 10, 1000, 10_000] {
+            let combat = fleets(ship_cap);
+            assert_eq!(combat.battle_honor, original.battle_honor, "{ship_cap} ships");
+            assert!(combat.max_ships.0 <= ship_cap && combat.max_ships.1 <= ship_cap);
+        }
+    }
+}
+
+#[test]
+fn small_fleets_are_worth_their_size() {
+    let mut pc = PaperClips { rng: PCRng::seeded(4), ..Default::default() };
+    pc.space.probe_count = 1e15;
+    pc.space.drifter_count = 4.2e7;
+    pc.combat.ship_cap = 1000;
+    pc.create_battle();
+    *pc.projects.status_mut(PROJECT_121) = ProjectStatus::Bought;
+    let drifters = pc.combat.max_ships.1;
+    assert!(drifters < DEFAULT_SHIP_CAP / 10, "{drifters} drifters");
+    // only the next battles get the new cap
+    pc.apply(Action::SetShipCap(1)).unwrap();
+
+    pc.combat.ship_count.1 = 0;
+    pc.check_for_battle_end();
+    assert_eq!(pc.combat.honor, drifters as Honor);
+}
+
+#[test]
+fn history_gets_saved_and_summed_up() {
+    let mut pc = battle(100);
+    while pc.combat.battles {
+        pc.update_combat();
+    }
+    pc.create_battle();
+    for _ in 0..10 {
+        pc.update_combat();
+    }
+
+    let loaded = PaperClips::from_save_str(&pc.to_save_string().unwrap()).unwrap();
+    assert_eq!(loaded.combat.history.len(), 2);
+    assert!(loaded.combat.history[0].winner.is_some());
+    assert_eq!(loaded.combat.history[1].winner, None);
+
+    let summary = WarSummary::new(&loaded.combat.history);
+    assert_eq!(summary.battles, 2);
+    assert_eq!(summary.won + summary.lost + summary.drawn, 1);
+    assert_eq!(summary.mean_duration, loaded.combat.history[0].duration as f64);
+    assert_eq!(summary.probes_lost, loaded.combat.history.iter().map(|record| record.probes_lost).sum::<f64>());
+}
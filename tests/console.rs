@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use paperclips::{
+    PaperClips,
+    action::{Action, Cheat},
+    console::{Console, ConsoleEntry, LogFormat, format_game_time},
+};
+
+#[test]
+fn history_outlives_the_visible_messages() {
+    let mut pc = PaperClips::default();
+    for _ in 0..250 {
+        pc.step();
+    }
+    pc.apply(Action::Cheat(Cheat::Trust)).unwrap();
+    for _ in 0..10 {
+        pc.step();
+        pc.log("filler");
+    }
+
+    assert_eq!(pc.console.messages.len(), pc.console.max_messages);
+    assert_eq!(pc.console.history.len(), 12);
+    assert_eq!(pc.console.history[0], ConsoleEntry { ticks: 1, message: "Welcome to Universal Paperclips".into() });
+
+    let cheats: Vec<_> = pc.console.search("LIZA").collect();
+    assert_eq!(cheats.len(), 1);
+    assert_eq!(cheats[0].ticks, 250);
+    assert_eq!(cheats[0].elapsed(), Duration::from_millis(2500));
+}
+
+#[test]
+fn history_is_capped() {
+    let mut console = Console { max_history: 3, ..Default::default() };
+    for i in 0..5 {
+        console.push(i, i.to_string());
+    }
+    let messages: Vec<_> = console.history.iter().map(|e| e.message.as_ref()).collect();
+    assert_eq!(messages, ["2", "3", "4"]);
+
+    // e.g. a save made with a bigger cap
+    console.max_history = 2;
+    console.push(5, "5");
+    let messages: Vec<_> = console.history.iter().map(|e| e.message.as_ref()).collect();
+    assert_eq!(messages, ["4", "5"]);
+}
+
+#[test]
+fn history_exports() {
+    let mut console = Console::default();
+    console.push(360_123, "One");
+    console.push(360_123, "Two");
+
+    assert_eq!(console.history_to_string(LogFormat::Text).unwrap(), "[1:00:01.23] One\n[1:00:01.23] Two\n");
+    let json: Vec<ConsoleEntry> = serde_json::from_str(&console.history_to_string(LogFormat::Json).unwrap()).unwrap();
+    assert_eq!(json, Vec::from(console.history));
+    assert_eq!(format_game_time(Duration::from_secs(59)), "0:00:59.00");
+}
@@ -0,0 +1,94 @@
+use paperclips::{
+    PaperClips,
+    action::Action,
+    end::Dismantle,
+    event::GameEvent,
+    progression::Progression,
+    project::{PROJECT_148, PROJECT_210, PROJECT_211, PROJECT_212, PROJECT_213, PROJECT_214, PROJECT_215, PROJECT_216, Project, ProjectStatus},
+};
+
+fn buyable(pc: &PaperClips, project: &Project) -> bool {
+    pc.projects.buyable_projects.iter().any(|&(_, p)| p == project)
+}
+
+/// A universe fully converted to clips, right after rejecting the Drift King
+fn rejected_drift() -> PaperClips {
+    let mut pc = PaperClips { progression: Progression::Endgame, milestones: 15, ..Default::default() };
+    pc.space.available_matter = 0.0;
+    pc.computational.comp_flag = true;
+    pc.computational.processors = 7;
+    pc.computational.memory = 300;
+    pc.projects.flag = true;
+    *pc.projects.status_mut(PROJECT_148) = ProjectStatus::Bought;
+
+    pc.space.probe_count = 1500.0;
+    pc.factory.harvester_level = 40.0;
+    pc.factory.wire_drone_level = 60.0;
+    pc.factory.factory_level = 12.0;
+    pc
+}
+
+/// Buys every project of the dismantling, up to the last inch of wire
+fn dismantle(pc: &mut PaperClips) {
+    for project in [&PROJECT_210, &PROJECT_211, &PROJECT_212, &PROJECT_213, &PROJECT_214, &PROJECT_215, &PROJECT_216] {
+        pc.computational.standard_ops = 200000.0;
+        let mut ticks = 0;
+        while !buyable(pc, project) {
+            pc.step();
+            ticks += 1;
+            assert!(ticks < 1000, "{} never showed up", project.name);
+        }
+        pc.step();
+        pc.apply(Action::BuyProject(project)).unwrap();
+    }
+}
+
+fn roll_credits(pc: &mut PaperClips) {
+    for _ in 0..1000 {
+        pc.step();
+    }
+    assert_eq!(pc.progression, Progression::Credits);
+}
+
+#[test]
+fn the_game_can_be_finished() {
+    let mut pc = rejected_drift();
+    let clips = pc.business.clips;
+    dismantle(&mut pc);
+
+    assert_eq!(pc.end.dismantle, Dismantle::Memory);
+    // some probes were lost on the way
+    assert!(pc.business.clips > clips + 1000.0);
+    assert_eq!((pc.space.probe_count, pc.factory.factory_level, pc.computational.processors, pc.computational.memory), (0.0, 0.0, 0, 0));
+
+    // every quantum chip gave back an inch of wire
+    let wire = pc.wire.count;
+    assert!(wire >= 10.0 + 7.0 + 300.0);
+
+    while pc.wire.count >= 1.0 {
+        pc.apply(Action::MakePaperclip).unwrap();
+        pc.step();
+    }
+    assert_eq!(pc.end.final_clips, wire);
+
+    roll_credits(&mut pc);
+    let transitions: Vec<_> = pc.take_events().into_iter().filter(|(_, e)| matches!(e, GameEvent::Progressed { .. })).collect();
+    assert!(matches!(transitions[..], [(_, GameEvent::Progressed { from: Progression::Endgame, to: Progression::Credits })]));
+    assert_eq!(pc.threnody.plays, 1);
+    assert_eq!(pc.console.messages.back().map(|m| &**m), Some("© 2017 Everybody House Games"));
+}
+
+#[test]
+fn credits_roll_with_a_fraction_of_wire_left() {
+    let mut pc = rejected_drift();
+    // wire made out of the last bits of matter isn't a round number
+    pc.wire.count = 0.375;
+    dismantle(&mut pc);
+    assert_eq!(pc.wire.count.fract(), 0.375);
+
+    while pc.apply(Action::MakePaperclip).is_ok() {
+        pc.step();
+    }
+    assert_eq!(pc.wire.count, 0.375);
+    roll_credits(&mut pc);
+}
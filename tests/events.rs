@@ -0,0 +1,59 @@
+use paperclips::{
+    PaperClips,
+    action::{Action, Cheat},
+    event::{GameEvent, MAX_EVENTS},
+    progression::Progression,
+    project::{PROJECT_1, PROJECT_6},
+};
+
+#[test]
+fn milestones_and_projects_are_emitted() {
+    let mut pc = PaperClips::default();
+    pc.apply(Action::Cheat(Cheat::Money)).unwrap();
+    pc.step();
+    let events = pc.take_events();
+    assert!(matches!(events.iter().next(), Some((1, GameEvent::MilestoneReached(0)))));
+    assert!(pc.take_events().is_empty());
+
+    pc.business.clipper_level = 1.0;
+    pc.computational.comp_flag = true;
+    pc.computational.standard_ops = 1000.0;
+    pc.projects.flag = true;
+    pc.step();
+    pc.apply(Action::BuyProject(&PROJECT_1)).unwrap();
+    assert!(pc.take_events().iter().any(|(_, e)| matches!(e, GameEvent::ProjectBought(p) if **p == PROJECT_1)));
+}
+
+#[test]
+fn trust_gains_carry_the_new_total() {
+    let mut pc = PaperClips::default();
+    pc.computational.trust = 4;
+    pc.computational.creativity = 100.0;
+    pc.computational.creativity_flag = true;
+    pc.projects.flag = true;
+    pc.step();
+    pc.apply(Action::BuyProject(&PROJECT_6)).unwrap();
+    let events = pc.take_events();
+    assert!(events.iter().any(|(_, e)| matches!(e, GameEvent::TrustGained { amount: 1, trust: 5 })));
+}
+
+#[test]
+fn progression_is_emitted_once() {
+    let mut pc = PaperClips::default();
+    assert!(pc.advance(Progression::PostHuman));
+    assert!(!pc.advance(Progression::Human));
+    let events = pc.take_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].1, GameEvent::Progressed { from: Progression::Human, to: Progression::PostHuman }));
+}
+
+#[test]
+fn old_events_get_dropped() {
+    let mut pc = PaperClips::default();
+    for gift in 0..MAX_EVENTS + 10 {
+        pc.emit(GameEvent::SwarmGift(gift as f64));
+    }
+    let events = pc.take_events();
+    assert_eq!(events.len(), MAX_EVENTS);
+    assert!(matches!(events[0].1, GameEvent::SwarmGift(10.0)));
+}
@@ -0,0 +1,90 @@
+use paperclips::{
+    PaperClips,
+    action::Action,
+    player::{Investor, Player, PriceOptimiser, Team, TourneyRunner},
+    project::ProjectStatus,
+    rng::PCRng,
+};
+
+fn play(seed: u64, ticks: u32) -> PaperClips {
+    let mut pc = PaperClips { rng: PCRng::seeded(seed), ..Default::default() };
+    let mut team = Team::reference();
+    for _ in 0..ticks {
+        pc.step_with(&mut team);
+    }
+    pc
+}
+
+#[test]
+fn reference_bots_get_the_business_going() {
+    let pc = play(5, 60_000);
+    assert!(pc.business.clips > 10_000.0, "only {} clips", pc.business.clips);
+    assert!(pc.business.clipper_level >= 10.0);
+    assert!(pc.business.marketing_lvl > 1);
+    assert!(pc.computational.processors + pc.computational.memory > 2);
+    assert!(pc.projects.statuses().contains(&ProjectStatus::Bought));
+}
+
+#[test]
+fn bots_are_deterministic() {
+    assert_eq!(play(9, 20_000).to_save_string().unwrap(), play(9, 20_000).to_save_string().unwrap());
+}
+
+#[test]
+fn price_goes_down_when_clips_pile_up() {
+    let mut pc = PaperClips::default();
+    pc.business.unsold_clips = 10_000.0;
+    pc.business.margin = 1.0;
+    pc.business.update_demand();
+    let mut bot = PriceOptimiser::default();
+    assert_eq!(bot.play(&pc), [Action::LowerPrice]);
+
+    pc.business.unsold_clips = 0.0;
+    pc.business.margin = 0.01;
+    pc.business.update_demand();
+    assert_eq!(bot.play(&pc), [Action::RaisePrice]);
+}
+
+#[test]
+fn tourney_runner_waits_for_the_engine() {
+    let mut pc = PaperClips::default();
+    assert!(TourneyRunner.play(&pc).is_empty());
+    pc.strategy.engine_flag = true;
+    pc.computational.operations = pc.strategy.tourney_cost;
+    let actions = TourneyRunner.play(&pc);
+    assert_eq!(actions.first(), Some(&Action::NewTourney));
+    assert_eq!(actions.last(), Some(&Action::RunTourney));
+    for action in actions {
+        pc.apply(action).unwrap();
+    }
+    assert!(pc.strategy.tourney_in_prog);
+}
+
+#[test]
+fn investor_deposits_then_withdraws() {
+    let mut pc = PaperClips::default();
+    let mut bot = Investor::default();
+    pc.ticks = bot.interval;
+    pc.business.funds = bot.min_deposit;
+    assert!(bot.play(&pc).is_empty());
+
+    pc.investments.engine_flag = true;
+    assert_eq!(bot.play(&pc), [Action::InvestDeposit]);
+    pc.apply(Action::InvestDeposit).unwrap();
+    assert_eq!(pc.investments.bankroll, bot.min_deposit);
+
+    pc.ticks += bot.interval / 2;
+    assert_eq!(bot.play(&pc), [Action::InvestWithdraw]);
+}
+
+#[test]
+fn investor_with_no_interval_deposits_every_tick() {
+    let mut pc = PaperClips::default();
+    pc.investments.engine_flag = true;
+    let mut bot = Investor { interval: 0, ..Default::default() };
+    pc.business.funds = bot.min_deposit;
+    for ticks in 1..3 {
+        pc.ticks = ticks;
+        assert_eq!(bot.play(&pc), [Action::InvestDeposit]);
+    }
+}
@@ -0,0 +1,58 @@
+use paperclips::{
+    PaperClips, Ticks,
+    player::Team,
+    project::{PROJECT_2, PROJECTS, ProjectStatus},
+    rng::PCRng,
+};
+
+/// When every project became buyable, the watch list gets rebuilt every tick if `rebuild`
+fn unlocks(seed: u64, ticks: Ticks, rebuild: bool) -> Vec<(Ticks, usize)> {
+    let mut pc = PaperClips { rng: PCRng::seeded(seed), ..Default::default() };
+    let mut team = Team::reference();
+    while pc.ticks < ticks {
+        if rebuild {
+            pc.projects.reset_watch_list();
+        }
+        pc.step_with(&mut team);
+    }
+    let mut unlocks: Vec<_> = pc.projects.buyable_projects.iter().map(|&(ticks, project)| (ticks, project.index)).collect();
+    // the bought ones aren't in `buyable_projects` anymore
+    unlocks.extend(pc.projects.statuses().iter().enumerate().filter(|&(_, &status)| status == ProjectStatus::Bought).map(|(i, _)| (0, i)));
+    unlocks.sort();
+    unlocks
+}
+
+#[test]
+fn watch_list_matches_a_full_evaluation() {
+    assert_eq!(unlocks(3, 200_000, false), unlocks(3, 200_000, true));
+}
+
+#[test]
+fn requirements_are_other_projects() {
+    for project in &PROJECTS {
+        for &i in project.requires {
+            assert!(i < PROJECTS.len(), "{} requires an unknown project", project.name);
+            assert_ne!(i, project.index, "{} requires itself", project.name);
+        }
+    }
+}
+
+#[test]
+fn relocked_projects_trigger_again() {
+    let mut pc = PaperClips::default();
+    let beg = |pc: &mut PaperClips| {
+        pc.wire.count = 0.0;
+        pc.business.funds = 0.0;
+        pc.business.unsold_clips = 0.0;
+        pc.manage_projects();
+        assert_eq!(pc.projects.status(PROJECT_2), ProjectStatus::Buyable);
+        let bpi = pc.projects.buyable_projects.iter().position(|&(_, project)| *project == PROJECT_2).unwrap();
+        pc.buy_project(bpi);
+    };
+    beg(&mut pc);
+    assert_eq!(pc.projects.status(PROJECT_2), ProjectStatus::Locked);
+    assert!(pc.wire.count > 0.0);
+    pc.manage_projects();
+    assert_eq!(pc.projects.status(PROJECT_2), ProjectStatus::Locked);
+    beg(&mut pc);
+}
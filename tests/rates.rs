@@ -0,0 +1,33 @@
+use paperclips::{PaperClips, progression::Progression, rates::{Flow, RATE_WINDOW}};
+
+#[test]
+fn clips_per_second() {
+    let mut pc = PaperClips::default();
+    assert_eq!(pc.rates.per_second(Flow::ClipsCreated), 0.0);
+
+    pc.wire.count = 1e6;
+    pc.business.clipper_level = 100.0;
+    pc.business.clipper_boost = 1.0;
+    for _ in 0..RATE_WINDOW * 2 {
+        pc.step();
+    }
+    // one clip per tick
+    assert!((pc.rates.per_second(Flow::ClipsCreated) - 100.0).abs() < 1e-6);
+    assert_eq!(pc.rates.per_second(Flow::ProbesSpawned), 0.0);
+}
+
+#[test]
+fn matter_and_wire_per_second() {
+    let mut pc = PaperClips { progression: Progression::PostHuman, ..Default::default() };
+    pc.factory.farm_level = 10;
+    pc.factory.harvester_level = 1.0;
+    pc.factory.wire_drone_level = 1.0;
+    for _ in 0..RATE_WINDOW {
+        pc.step();
+    }
+
+    let acquired = pc.rates.per_second(Flow::MatterAcquired);
+    assert!(acquired > 0.0);
+    assert!((acquired - pc.factory.acquired_matter_per_tick * RATE_WINDOW as f64).abs() / acquired < 1e-9);
+    assert!(pc.rates.per_second(Flow::WireProduced) > 0.0);
+}
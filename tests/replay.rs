@@ -0,0 +1,105 @@
+use paperclips::{
+    PaperClips, Ticks,
+    action::{Action, Cheat},
+    replay::Replay,
+    rng::{PCRng, RngKind},
+};
+
+fn seeded(seed: u64) -> PaperClips {
+    PaperClips { rng: PCRng::seeded(seed), ..Default::default() }
+}
+
+fn state(pc: &PaperClips) -> String {
+    pc.to_save_string().unwrap()
+}
+
+/// A simple player, enough to get through the first projects, investments and tournaments
+fn next_actions(pc: &PaperClips) -> Vec<Action> {
+    let mut actions = Vec::new();
+
+    if pc.ticks == 1 {
+        actions.extend([Action::Cheat(Cheat::Money), Action::Cheat(Cheat::Trust), Action::Cheat(Cheat::Ops)]);
+    }
+    if pc.ticks.is_multiple_of(3) && pc.wire.count >= 1.0 {
+        actions.push(Action::MakePaperclip);
+    }
+    if pc.wire.count < 500.0 && pc.business.funds >= pc.wire.cost {
+        actions.push(Action::BuyWire);
+    }
+    if pc.business.clipper_flag && pc.business.funds >= pc.business.clipper_cost && pc.business.clipper_level < 50.0 {
+        actions.push(Action::MakeClipper);
+    }
+    if pc.ticks.is_multiple_of(500) {
+        actions.push(if pc.business.unsold_clips > 1000.0 { Action::LowerPrice } else { Action::RaisePrice });
+    }
+    if let Some(&(_, project)) = pc.projects.buyable_projects.iter().find(|(_, p)| (p.cost.1)(pc)) {
+        actions.push(Action::BuyProject(project));
+    }
+    let c = &pc.computational;
+    if c.trust > (c.processors + c.memory) as i32 {
+        actions.push(if c.processors < c.memory { Action::AddProcessor } else { Action::AddMemory });
+    }
+    if pc.investments.engine_flag && pc.ticks.is_multiple_of(1000) {
+        actions.push(Action::InvestDeposit);
+    }
+    if pc.strategy.engine_flag && !pc.strategy.tourney_in_prog && pc.computational.operations >= pc.strategy.tourney_cost {
+        actions.extend([Action::NewTourney, Action::RunTourney]);
+    }
+
+    actions
+}
+
+fn record(initial: PaperClips, ticks: Ticks) -> (Replay, PaperClips) {
+    let mut pc = initial.clone();
+    let mut replay = Replay::new(initial);
+    for _ in 0..ticks {
+        replay.step(&mut pc);
+        for action in next_actions(&pc) {
+            // the simple player doesn't keep track of what earlier actions spent
+            let _ = replay.record(&mut pc, action);
+        }
+    }
+    (replay, pc)
+}
+
+#[test]
+fn same_seed_same_game() {
+    let (_, a) = record(seeded(42), 20_000);
+    let (_, b) = record(seeded(42), 20_000);
+    assert_eq!(state(&a), state(&b));
+
+    let (_, c) = record(seeded(43), 20_000);
+    assert_ne!(state(&a), state(&c));
+}
+
+#[test]
+fn replay_is_bit_identical() {
+    let (replay, pc) = record(seeded(7), 30_000);
+    assert!(replay.actions.len() > 100);
+    assert_eq!(state(&replay.play()), state(&pc));
+}
+
+#[test]
+fn replay_survives_a_file_round_trip() {
+    let (replay, pc) = record(seeded(1234), 10_000);
+    let loaded = Replay::from_replay_str(&replay.to_replay_string().unwrap()).unwrap();
+    assert_eq!(loaded.actions, replay.actions);
+    assert_eq!(state(&loaded.play()), state(&pc));
+}
+
+#[test]
+fn replay_from_a_loaded_game() {
+    let (_, midway) = record(seeded(99), 5_000);
+    let (replay, pc) = record(midway, 5_000);
+    assert_eq!(state(&replay.play()), state(&pc));
+}
+
+#[test]
+fn best_and_worst_are_reproducible() {
+    for rng_kind in [RngKind::Best, RngKind::Worst] {
+        let initial = PaperClips { rng: PCRng { rng_kind, fallback: 5 }, ..Default::default() };
+        let (_, a) = record(initial.clone(), 10_000);
+        let (_, b) = record(initial, 10_000);
+        assert_eq!(state(&a), state(&b));
+    }
+}
@@ -0,0 +1,38 @@
+use paperclips::{
+    PaperClips,
+    player::Team,
+    project::{PROJECT_1, PROJECTS, ProjectStatus},
+    rng::PCRng,
+};
+
+#[test]
+fn saves_round_trip() {
+    let mut pc = PaperClips { rng: PCRng::seeded(1), ..Default::default() };
+    let mut team = Team::reference();
+    while pc.ticks < 60_000 {
+        pc.step_with(&mut team);
+    }
+    let save = pc.to_save_string().unwrap();
+    let loaded = PaperClips::from_save_str(&save).unwrap();
+    assert_eq!(loaded.to_save_string().unwrap(), save);
+    assert_eq!(loaded.ticks, pc.ticks);
+}
+
+#[test]
+fn saves_with_fewer_projects_load() {
+    let mut pc = PaperClips::default();
+    *pc.projects.status_mut(PROJECT_1) = ProjectStatus::Bought;
+    let save = pc.to_save_string().unwrap();
+
+    // what a save made before the last 10 projects were added looks like
+    let start = save.find("statuses:[").unwrap() + "statuses:[".len();
+    let end = start + save[start..].find(']').unwrap();
+    let statuses: Vec<_> = save[start..end].split(',').collect();
+    assert_eq!(statuses.len(), PROJECTS.len());
+    let older = format!("{}{}{}", &save[..start], statuses[..PROJECTS.len() - 10].join(","), &save[end..]);
+
+    let loaded = PaperClips::from_save_str(&older).unwrap();
+    assert_eq!(loaded.projects.status(PROJECT_1), ProjectStatus::Bought);
+    assert_eq!(loaded.projects.statuses(), pc.projects.statuses());
+    assert_eq!(loaded.to_save_string().unwrap(), save);
+}
@@ -0,0 +1,60 @@
+use paperclips::{PaperClips, progression::Progression, replay::{REPLAY_VERSION, Replay}, save::{SAVE_VERSION, SaveError}};
+
+/// Turns a current save into what version 1 wrote
+fn to_v1(pc: &PaperClips, milestone_flag: u8, human_flag: bool, space_flag: bool) -> String {
+    let save = pc.to_save_string().unwrap();
+    let progression = format!("progression:{:?},milestones:{},", pc.progression, pc.milestones);
+    assert!(save.contains(&progression));
+    save.replacen(&format!("(version:{SAVE_VERSION},"), "(version:1,", 1)
+        .replacen(&progression, &format!("milestone_flag:{milestone_flag},human_flag:{human_flag},"), 1)
+        .replacen("space:(", &format!("space:(space_flag:{space_flag},"), 1)
+}
+
+#[test]
+fn version_1_flags_become_the_progression() {
+    let pc = PaperClips { ticks: 1234, ..Default::default() };
+    for (milestone_flag, human_flag, space_flag, progression) in [
+        (3, true, false, Progression::Human),
+        (8, false, false, Progression::PostHuman),
+        (14, false, true, Progression::Space),
+        (15, false, true, Progression::Endgame),
+        (18, false, true, Progression::Credits),
+    ] {
+        let loaded = PaperClips::from_save_str(&to_v1(&pc, milestone_flag, human_flag, space_flag)).unwrap();
+        assert_eq!(loaded.progression, progression);
+        assert_eq!(loaded.milestones, milestone_flag.min(15));
+        assert_eq!(loaded.ticks, 1234);
+    }
+}
+
+#[test]
+fn current_saves_round_trip() {
+    let pc = PaperClips { progression: Progression::Space, milestones: 13, ..Default::default() };
+    let loaded = PaperClips::from_save_str(&pc.to_save_string().unwrap()).unwrap();
+    assert_eq!(loaded.progression, Progression::Space);
+    assert_eq!(loaded.milestones, 13);
+    assert!(loaded.space_flag() && !loaded.human_flag());
+}
+
+#[test]
+fn replay_states_get_migrated() {
+    let pc = PaperClips { ticks: 1234, ..Default::default() };
+    let v1 = to_v1(&pc, 8, false, false);
+    let state = v1.strip_prefix("(version:1,paperclips:").and_then(|s| s.strip_suffix(')')).unwrap();
+    let replay = format!("(replay_version:{REPLAY_VERSION},save_version:1,initial:{state},actions:[],end:1300)");
+
+    let replay = Replay::from_replay_str(&replay).unwrap();
+    assert_eq!(replay.initial.progression, Progression::PostHuman);
+    assert_eq!(replay.initial.ticks, 1234);
+    assert_eq!(replay.end, 1300);
+
+    let current = Replay::from_replay_str(&replay.to_replay_string().unwrap()).unwrap();
+    assert_eq!(current.initial.progression, Progression::PostHuman);
+}
+
+#[test]
+fn unknown_replay_versions_are_rejected() {
+    let replay = Replay::new(PaperClips::default()).to_replay_string().unwrap();
+    let newer = replay.replacen(&format!("(replay_version:{REPLAY_VERSION},"), "(replay_version:99,", 1);
+    assert!(matches!(Replay::from_replay_str(&newer), Err(SaveError::UnsupportedReplayVersion(99))));
+}
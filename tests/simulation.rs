@@ -0,0 +1,79 @@
+use std::{thread, time::{Duration, Instant}};
+
+use paperclips::{
+    PaperClips,
+    action::{Action, ActionError},
+    event::{GameEvent, MAX_EVENTS},
+    rng::{PCRng, RngKind},
+    simulation::{Command, Simulation},
+    warp::Speed,
+};
+
+/// Polls `condition` for a few seconds
+fn wait_for(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(start.elapsed() < Duration::from_secs(10), "timed out");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn actions_are_recorded_while_it_runs() {
+    let simulation = Simulation::spawn(PaperClips { rng: PCRng::seeded(4), ..Default::default() }, || {});
+    simulation.act(Action::MakePaperclip);
+    simulation.act(Action::MakeMegaClipper);
+    simulation.send(Command::SetSpeed(Speed::Max));
+    wait_for(|| simulation.snapshot().ticks >= 1000);
+
+    let mut errors = Vec::new();
+    wait_for(|| { errors.extend(simulation.errors()); !errors.is_empty() });
+    assert!(matches!(errors[..], [ActionError::Locked]), "{errors:?}");
+
+    let replay = simulation.replay().unwrap();
+    assert_eq!(replay.actions.len(), 1);
+    assert_eq!(replay.actions[0].1, Action::MakePaperclip);
+    assert!(replay.end >= 1000);
+    assert!(replay.play().business.clips >= 1.0);
+    assert!(!simulation.telemetry().unwrap().rows.is_empty());
+}
+
+#[test]
+fn started_games_replace_the_snapshot_right_away() {
+    let mut simulation = Simulation::spawn(PaperClips::default(), || {});
+    simulation.send(Command::SetSpeed(Speed::Max));
+    wait_for(|| simulation.snapshot().ticks >= 100);
+
+    let mut pc = PaperClips::default();
+    pc.business.clips = 1234.0;
+    simulation.start(pc);
+    assert_eq!(simulation.snapshot().business.clips, 1234.0);
+    // the game gets recorded, so it can't keep the thread rng
+    assert!(matches!(simulation.snapshot().rng.rng_kind, RngKind::Seeded(_)));
+    thread::sleep(Duration::from_millis(50));
+    assert!(simulation.snapshot().business.clips >= 1234.0);
+    assert!(simulation.replay().unwrap().actions.is_empty());
+}
+
+#[test]
+fn events_come_out_of_the_channel_instead_of_the_snapshots() {
+    let mut pc = PaperClips::default();
+    pc.business.funds = 100.0;
+    pc.business.clips = 600.0;
+    let simulation = Simulation::spawn(pc, || {});
+    simulation.send(Command::SetSpeed(Speed::Max));
+
+    let mut events = Vec::new();
+    wait_for(|| { events.extend(simulation.events()); events.len() >= 2 });
+    assert!(matches!(events[..], [(1, GameEvent::MilestoneReached(0)), (1, GameEvent::MilestoneReached(1)), ..]), "{events:?}");
+    assert!(simulation.snapshot().events.is_empty());
+}
+
+#[test]
+fn unread_events_dont_pile_up() {
+    let simulation = Simulation::spawn(PaperClips { rng: PCRng::seeded(4), ..Default::default() }, || {});
+    simulation.send(Command::SetAutopilot(true));
+    simulation.send(Command::SetSpeed(Speed::Max));
+    wait_for(|| simulation.snapshot().ticks >= 20_000);
+    assert!(simulation.events().count() <= MAX_EVENTS);
+}
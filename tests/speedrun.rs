@@ -0,0 +1,31 @@
+use paperclips::{
+    PaperClips,
+    progression::Progression,
+    rng::PCRng,
+    speedrun::{Goal, Policy, Search, evaluate, record},
+};
+
+#[test]
+fn goals_parse() {
+    assert_eq!("milestone:3".parse(), Ok(Goal::Milestone(3)));
+    assert_eq!("space".parse(), Ok(Goal::Progression(Progression::Space)));
+    assert!("milestone:x".parse::<Goal>().is_err());
+    assert!("victory".parse::<Goal>().is_err());
+}
+
+#[test]
+fn search_never_does_worse_than_the_default_policy() {
+    let initial = PaperClips { rng: PCRng::seeded(0), ..Default::default() };
+    let goal = Goal::Milestone(2);
+    let search = Search { population: 4, elites: 2, generations: 2, threads: 2, ..Search::new(goal, 20_000) };
+
+    let default = evaluate(&initial, Policy::default(), goal, search.max_ticks);
+    let best = search.run(&initial, |_, _| {});
+    let ticks = best.reached.expect("milestone 2 wasn't reached");
+    assert!(ticks <= default.reached.unwrap(), "{ticks} ticks, the default policy needed {:?}", default.reached);
+    assert_eq!(search.run(&initial, |_, _| {}), best);
+
+    // the replay gets there just as fast
+    let replay = record(&initial, best.policy, goal, ticks);
+    assert!(goal.reached(&replay.play_until(ticks)));
+}
@@ -0,0 +1,32 @@
+use paperclips::{
+    PaperClips,
+    stats::{MAX_SAMPLES, STATS_INTERVAL, Sample, Stat, Stats},
+};
+use strum::EnumCount;
+
+#[test]
+fn stats_are_sampled_every_interval() {
+    let mut pc = PaperClips::default();
+    pc.business.funds = 123.0;
+    for _ in 0..STATS_INTERVAL * 3 {
+        pc.step();
+    }
+    let ticks: Vec<_> = pc.stats.series(Stat::Funds).map(|(ticks, _)| ticks).collect();
+    assert_eq!(ticks, [STATS_INTERVAL, STATS_INTERVAL * 2, STATS_INTERVAL * 3]);
+    assert!(pc.stats.series(Stat::Funds).all(|(_, funds)| funds == 123.0));
+}
+
+#[test]
+fn long_runs_get_downsampled() {
+    let mut stats = Stats::default();
+    let mut ticks = 0;
+    while stats.interval == STATS_INTERVAL {
+        ticks += stats.interval;
+        stats.push(Sample { ticks, values: [ticks as f64; Stat::COUNT] });
+    }
+
+    assert_eq!(stats.interval, STATS_INTERVAL * 2);
+    assert_eq!(stats.samples.len(), MAX_SAMPLES / 2);
+    assert!(stats.samples.iter().all(|s| s.ticks % stats.interval == 0));
+    assert_eq!(stats.samples.last().unwrap().ticks, MAX_SAMPLES as u128 * STATS_INTERVAL);
+}
@@ -0,0 +1,73 @@
+use paperclips::{
+    PaperClips,
+    stats::Stat,
+    telemetry::{Telemetry, TelemetryRow},
+};
+
+fn run(interval: u128, ticks: u128) -> Telemetry {
+    let mut pc = PaperClips::default();
+    pc.business.funds = 1e20;
+    let mut telemetry = Telemetry::new(interval);
+    telemetry.record(&pc);
+    for _ in 0..ticks {
+        pc.step();
+        telemetry.record(&pc);
+    }
+    telemetry
+}
+
+#[test]
+fn a_row_per_interval() {
+    let telemetry = run(50, 200);
+    let ticks: Vec<_> = telemetry.rows.iter().map(|row| row.ticks).collect();
+    assert_eq!(ticks, [0, 50, 100, 150, 200]);
+    assert!(telemetry.rows.iter().all(|row| row.get(Stat::Funds) == 1e20));
+}
+
+#[test]
+fn thinning_keeps_every_other_row() {
+    let mut telemetry = run(50, 300);
+    telemetry.thin();
+    assert_eq!(telemetry.interval, 100);
+    let ticks: Vec<_> = telemetry.rows.iter().map(|row| row.ticks).collect();
+    assert_eq!(ticks, [0, 100, 200, 300]);
+}
+
+#[test]
+fn csv_has_a_header_and_a_line_per_row() {
+    let csv = run(100, 300).to_csv();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("ticks,progression,clips,"));
+    for line in &lines {
+        assert_eq!(line.split(',').count(), TelemetryRow::columns().count() + 2);
+    }
+    assert!(lines[1].starts_with("0,Human,"));
+    assert!(lines[1].contains(",1e20,"));
+}
+
+#[test]
+fn json_has_every_column() {
+    let telemetry = run(100, 100);
+    let json: serde_json::Value = serde_json::from_str(&telemetry.to_json().unwrap()).unwrap();
+    let rows = json.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    for column in TelemetryRow::columns() {
+        assert!(rows[1].get(column).is_some(), "missing {column}");
+    }
+    assert_eq!(rows[1]["ticks"], 100);
+    assert_eq!(rows[1]["funds"], 1e20);
+    assert_eq!(TelemetryRow::new(&PaperClips::default()).progression, Default::default());
+}
+
+#[test]
+fn rows_start_with_a_sample_of_the_stats() {
+    let mut pc = PaperClips::default();
+    for _ in 0..250 {
+        pc.step();
+    }
+    let row = TelemetryRow::new(&pc);
+    let sample = pc.sample();
+    assert_eq!((row.ticks, row.stats), (sample.ticks, sample.values));
+    assert!(Stat::ALL.iter().all(|&stat| row.get(stat) == stat.value(&pc)));
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use paperclips::{
+    PaperClips,
+    computational::Computational,
+    rng::PCRng,
+    save::SaveFile,
+    warp::CatchUp,
+    wire::Wire,
+};
+
+fn thinking() -> Computational {
+    Computational {
+        comp_flag: true,
+        creativity_flag: true,
+        processors: 7,
+        memory: 3,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn operations_and_creativity_match_the_ticks() {
+    for ticks in [1, 100, 4_285, 4_286, 50_000] {
+        let mut ticked = thinking();
+        for _ in 0..ticks {
+            ticked.calculate_operations();
+            if ticked.operations >= ticked.max_operations() as f64 {
+                ticked.calculate_creativity();
+            }
+        }
+        let mut advanced = thinking();
+        advanced.advance(ticks);
+
+        assert!((ticked.standard_ops - advanced.standard_ops).abs() < 1e-6, "{ticks} ticks");
+        assert_eq!(ticked.operations, advanced.operations, "{ticks} ticks");
+        assert_eq!(ticked.creativity_counter, advanced.creativity_counter, "{ticks} ticks");
+        assert!((ticked.creativity - advanced.creativity).abs() < 1e-6, "{ticks} ticks");
+    }
+}
+
+#[test]
+fn fast_creativity_matches_the_ticks() {
+    let fast = Computational { creativity_speed: 1000.0, creativity_counter: 3, ..thinking() };
+    let mut ticked = fast;
+    for _ in 0..1000 {
+        ticked.calculate_creativity();
+    }
+    let mut advanced = fast;
+    advanced.advance_creativity(1000);
+    assert!((ticked.creativity - advanced.creativity).abs() < 1e-9);
+    assert_eq!(ticked.creativity_counter, advanced.creativity_counter);
+}
+
+#[test]
+fn wire_price_keeps_its_distribution() {
+    let wire = Wire { base_price: 30.0, ..Default::default() };
+    let mut rng = PCRng::seeded(3);
+    let mut ticked = wire;
+    for _ in 0..100_000 {
+        ticked.adjust_wire_price(&mut rng);
+    }
+    let mut advanced = wire;
+    advanced.adjust_wire_price_n(&mut rng, 100_000);
+
+    // the decay doesn't depend on the rng
+    assert!((ticked.base_price - advanced.base_price).abs() < 1e-9);
+    assert_eq!(ticked.price_timer, advanced.price_timer);
+    // 1.5% of the adjustments change the price
+    for counter in [ticked.price_counter, advanced.price_counter] {
+        assert!((1_300..1_700).contains(&counter), "{counter} price changes");
+    }
+}
+
+#[test]
+fn catch_up_skips_what_the_budget_doesnt_allow() {
+    let mut pc = PaperClips { rng: PCRng::seeded(1), ..Default::default() };
+    pc.computational = thinking();
+
+    assert_eq!(pc.catch_up(500, Duration::from_secs(60)), CatchUp { simulated: 500, skipped: 0 });
+    assert_eq!(pc.ticks, 500);
+
+    let clips = pc.business.clips;
+    assert_eq!(pc.catch_up(100_000, Duration::ZERO), CatchUp { simulated: 0, skipped: 100_000 });
+    assert_eq!(pc.ticks, 100_500);
+    assert_eq!(pc.business.clips, clips);
+    assert_eq!(pc.computational.operations, pc.computational.max_operations() as f64);
+    assert!(pc.computational.creativity > 0.0);
+}
+
+#[test]
+fn only_save_files_know_when_they_were_made() {
+    let pc = PaperClips::default();
+    assert_eq!(SaveFile::from_save_str(&pc.to_save_string().unwrap()).unwrap().time_away(), None);
+
+    let path = std::env::temp_dir().join(format!("paperclips_warp_{}.ron", std::process::id()));
+    pc.save_to_file(&path).unwrap();
+    let save = SaveFile::load_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(save.unwrap().time_away().unwrap() < Duration::from_secs(60));
+}